@@ -362,9 +362,12 @@ fn main() {
         .write_all(aggregated_report_columns.iter().join(",").as_bytes())
         .expect("CSV header row could not be written");
 
-    let total_language_count = Language::iter().count();
+    let languages_with_test_data = Language::iter()
+        .filter(|it| get_test_data_directory(it).is_some())
+        .collect_vec();
+    let total_language_count = languages_with_test_data.len();
 
-    for (idx, language) in Language::iter().enumerate() {
+    for (idx, language) in languages_with_test_data.into_iter().enumerate() {
         println!(
             "Writing reports for {:?}... ({}/{})",
             &language,
@@ -445,7 +448,7 @@ fn main() {
 }
 
 fn get_file_content<'a>(file_name: &'a str, language: &'a Language) -> Vec<&'a str> {
-    let directory = get_test_data_directory(language);
+    let directory = get_test_data_directory(language).unwrap();
     directory
         .get_file(file_name)
         .unwrap()
@@ -524,82 +527,95 @@ fn map_whatlang_to_lingua(language: Option<Lang>) -> Option<Language> {
     }
 }
 
-fn get_test_data_directory(language: &Language) -> Dir<'static> {
+fn get_test_data_directory(language: &Language) -> Option<Dir<'static>> {
     match language {
-        &Language::Afrikaans => AFRIKAANS_TESTDATA_DIRECTORY,
-        &Language::Albanian => ALBANIAN_TESTDATA_DIRECTORY,
-        &Language::Arabic => ARABIC_TESTDATA_DIRECTORY,
-        &Language::Armenian => ARMENIAN_TESTDATA_DIRECTORY,
-        &Language::Azerbaijani => AZERBAIJANI_TESTDATA_DIRECTORY,
-        &Language::Basque => BASQUE_TESTDATA_DIRECTORY,
-        &Language::Belarusian => BELARUSIAN_TESTDATA_DIRECTORY,
-        &Language::Bengali => BENGALI_TESTDATA_DIRECTORY,
-        &Language::Bokmal => BOKMAL_TESTDATA_DIRECTORY,
-        &Language::Bosnian => BOSNIAN_TESTDATA_DIRECTORY,
-        &Language::Bulgarian => BULGARIAN_TESTDATA_DIRECTORY,
-        &Language::Catalan => CATALAN_TESTDATA_DIRECTORY,
-        &Language::Chinese => CHINESE_TESTDATA_DIRECTORY,
-        &Language::Croatian => CROATIAN_TESTDATA_DIRECTORY,
-        &Language::Czech => CZECH_TESTDATA_DIRECTORY,
-        &Language::Danish => DANISH_TESTDATA_DIRECTORY,
-        &Language::Dutch => DUTCH_TESTDATA_DIRECTORY,
-        &Language::English => ENGLISH_TESTDATA_DIRECTORY,
-        &Language::Esperanto => ESPERANTO_TESTDATA_DIRECTORY,
-        &Language::Estonian => ESTONIAN_TESTDATA_DIRECTORY,
-        &Language::Finnish => FINNISH_TESTDATA_DIRECTORY,
-        &Language::French => FRENCH_TESTDATA_DIRECTORY,
-        &Language::Ganda => GANDA_TESTDATA_DIRECTORY,
-        &Language::Georgian => GEORGIAN_TESTDATA_DIRECTORY,
-        &Language::German => GERMAN_TESTDATA_DIRECTORY,
-        &Language::Greek => GREEK_TESTDATA_DIRECTORY,
-        &Language::Gujarati => GUJARATI_TESTDATA_DIRECTORY,
-        &Language::Hebrew => HEBREW_TESTDATA_DIRECTORY,
-        &Language::Hindi => HINDI_TESTDATA_DIRECTORY,
-        &Language::Hungarian => HUNGARIAN_TESTDATA_DIRECTORY,
-        &Language::Icelandic => ICELANDIC_TESTDATA_DIRECTORY,
-        &Language::Indonesian => INDONESIAN_TESTDATA_DIRECTORY,
-        &Language::Irish => IRISH_TESTDATA_DIRECTORY,
-        &Language::Italian => ITALIAN_TESTDATA_DIRECTORY,
-        &Language::Japanese => JAPANESE_TESTDATA_DIRECTORY,
-        &Language::Kazakh => KAZAKH_TESTDATA_DIRECTORY,
-        &Language::Korean => KOREAN_TESTDATA_DIRECTORY,
-        &Language::Latin => LATIN_TESTDATA_DIRECTORY,
-        &Language::Latvian => LATVIAN_TESTDATA_DIRECTORY,
-        &Language::Lithuanian => LITHUANIAN_TESTDATA_DIRECTORY,
-        &Language::Macedonian => MACEDONIAN_TESTDATA_DIRECTORY,
-        &Language::Malay => MALAY_TESTDATA_DIRECTORY,
-        &Language::Maori => MAORI_TESTDATA_DIRECTORY,
-        &Language::Marathi => MARATHI_TESTDATA_DIRECTORY,
-        &Language::Mongolian => MONGOLIAN_TESTDATA_DIRECTORY,
-        &Language::Nynorsk => NYNORSK_TESTDATA_DIRECTORY,
-        &Language::Persian => PERSIAN_TESTDATA_DIRECTORY,
-        &Language::Polish => POLISH_TESTDATA_DIRECTORY,
-        &Language::Portuguese => PORTUGUESE_TESTDATA_DIRECTORY,
-        &Language::Punjabi => PUNJABI_TESTDATA_DIRECTORY,
-        &Language::Romanian => ROMANIAN_TESTDATA_DIRECTORY,
-        &Language::Russian => RUSSIAN_TESTDATA_DIRECTORY,
-        &Language::Serbian => SERBIAN_TESTDATA_DIRECTORY,
-        &Language::Shona => SHONA_TESTDATA_DIRECTORY,
-        &Language::Slovak => SLOVAK_TESTDATA_DIRECTORY,
-        &Language::Slovene => SLOVENE_TESTDATA_DIRECTORY,
-        &Language::Somali => SOMALI_TESTDATA_DIRECTORY,
-        &Language::Sotho => SOTHO_TESTDATA_DIRECTORY,
-        &Language::Spanish => SPANISH_TESTDATA_DIRECTORY,
-        &Language::Swahili => SWAHILI_TESTDATA_DIRECTORY,
-        &Language::Swedish => SWEDISH_TESTDATA_DIRECTORY,
-        &Language::Tagalog => TAGALOG_TESTDATA_DIRECTORY,
-        &Language::Tamil => TAMIL_TESTDATA_DIRECTORY,
-        &Language::Telugu => TELUGU_TESTDATA_DIRECTORY,
-        &Language::Thai => THAI_TESTDATA_DIRECTORY,
-        &Language::Tsonga => TSONGA_TESTDATA_DIRECTORY,
-        &Language::Tswana => TSWANA_TESTDATA_DIRECTORY,
-        &Language::Turkish => TURKISH_TESTDATA_DIRECTORY,
-        &Language::Ukrainian => UKRAINIAN_TESTDATA_DIRECTORY,
-        &Language::Urdu => URDU_TESTDATA_DIRECTORY,
-        &Language::Vietnamese => VIETNAMESE_TESTDATA_DIRECTORY,
-        &Language::Welsh => WELSH_TESTDATA_DIRECTORY,
-        &Language::Xhosa => XHOSA_TESTDATA_DIRECTORY,
-        &Language::Yoruba => YORUBA_TESTDATA_DIRECTORY,
-        &Language::Zulu => ZULU_TESTDATA_DIRECTORY,
+        &Language::Afrikaans => Some(AFRIKAANS_TESTDATA_DIRECTORY),
+        &Language::Albanian => Some(ALBANIAN_TESTDATA_DIRECTORY),
+        &Language::Arabic => Some(ARABIC_TESTDATA_DIRECTORY),
+        &Language::Armenian => Some(ARMENIAN_TESTDATA_DIRECTORY),
+        &Language::Azerbaijani => Some(AZERBAIJANI_TESTDATA_DIRECTORY),
+        &Language::Basque => Some(BASQUE_TESTDATA_DIRECTORY),
+        &Language::Belarusian => Some(BELARUSIAN_TESTDATA_DIRECTORY),
+        &Language::Bengali => Some(BENGALI_TESTDATA_DIRECTORY),
+        &Language::Bokmal => Some(BOKMAL_TESTDATA_DIRECTORY),
+        &Language::Bosnian => Some(BOSNIAN_TESTDATA_DIRECTORY),
+        &Language::Bulgarian => Some(BULGARIAN_TESTDATA_DIRECTORY),
+        &Language::Catalan => Some(CATALAN_TESTDATA_DIRECTORY),
+        &Language::Chinese => Some(CHINESE_TESTDATA_DIRECTORY),
+        &Language::Croatian => Some(CROATIAN_TESTDATA_DIRECTORY),
+        &Language::Czech => Some(CZECH_TESTDATA_DIRECTORY),
+        &Language::Danish => Some(DANISH_TESTDATA_DIRECTORY),
+        &Language::Dutch => Some(DUTCH_TESTDATA_DIRECTORY),
+        &Language::English => Some(ENGLISH_TESTDATA_DIRECTORY),
+        &Language::Esperanto => Some(ESPERANTO_TESTDATA_DIRECTORY),
+        &Language::Estonian => Some(ESTONIAN_TESTDATA_DIRECTORY),
+        &Language::Finnish => Some(FINNISH_TESTDATA_DIRECTORY),
+        &Language::French => Some(FRENCH_TESTDATA_DIRECTORY),
+        &Language::Ganda => Some(GANDA_TESTDATA_DIRECTORY),
+        &Language::Georgian => Some(GEORGIAN_TESTDATA_DIRECTORY),
+        &Language::German => Some(GERMAN_TESTDATA_DIRECTORY),
+        &Language::Greek => Some(GREEK_TESTDATA_DIRECTORY),
+        &Language::Gujarati => Some(GUJARATI_TESTDATA_DIRECTORY),
+        &Language::Hebrew => Some(HEBREW_TESTDATA_DIRECTORY),
+        &Language::Hindi => Some(HINDI_TESTDATA_DIRECTORY),
+        &Language::Hungarian => Some(HUNGARIAN_TESTDATA_DIRECTORY),
+        &Language::Icelandic => Some(ICELANDIC_TESTDATA_DIRECTORY),
+        &Language::Indonesian => Some(INDONESIAN_TESTDATA_DIRECTORY),
+        &Language::Irish => Some(IRISH_TESTDATA_DIRECTORY),
+        &Language::Italian => Some(ITALIAN_TESTDATA_DIRECTORY),
+        &Language::Japanese => Some(JAPANESE_TESTDATA_DIRECTORY),
+        &Language::Kazakh => Some(KAZAKH_TESTDATA_DIRECTORY),
+        &Language::Korean => Some(KOREAN_TESTDATA_DIRECTORY),
+        &Language::Latin => Some(LATIN_TESTDATA_DIRECTORY),
+        &Language::Latvian => Some(LATVIAN_TESTDATA_DIRECTORY),
+        &Language::Lithuanian => Some(LITHUANIAN_TESTDATA_DIRECTORY),
+        &Language::Macedonian => Some(MACEDONIAN_TESTDATA_DIRECTORY),
+        &Language::Malay => Some(MALAY_TESTDATA_DIRECTORY),
+        &Language::Maori => Some(MAORI_TESTDATA_DIRECTORY),
+        &Language::Marathi => Some(MARATHI_TESTDATA_DIRECTORY),
+        &Language::Mongolian => Some(MONGOLIAN_TESTDATA_DIRECTORY),
+        &Language::Nynorsk => Some(NYNORSK_TESTDATA_DIRECTORY),
+        &Language::Persian => Some(PERSIAN_TESTDATA_DIRECTORY),
+        &Language::Polish => Some(POLISH_TESTDATA_DIRECTORY),
+        &Language::Portuguese => Some(PORTUGUESE_TESTDATA_DIRECTORY),
+        &Language::Punjabi => Some(PUNJABI_TESTDATA_DIRECTORY),
+        &Language::Romanian => Some(ROMANIAN_TESTDATA_DIRECTORY),
+        &Language::Russian => Some(RUSSIAN_TESTDATA_DIRECTORY),
+        &Language::Serbian => Some(SERBIAN_TESTDATA_DIRECTORY),
+        &Language::Shona => Some(SHONA_TESTDATA_DIRECTORY),
+        &Language::Slovak => Some(SLOVAK_TESTDATA_DIRECTORY),
+        &Language::Slovene => Some(SLOVENE_TESTDATA_DIRECTORY),
+        &Language::Somali => Some(SOMALI_TESTDATA_DIRECTORY),
+        &Language::Sotho => Some(SOTHO_TESTDATA_DIRECTORY),
+        &Language::Spanish => Some(SPANISH_TESTDATA_DIRECTORY),
+        &Language::Swahili => Some(SWAHILI_TESTDATA_DIRECTORY),
+        &Language::Swedish => Some(SWEDISH_TESTDATA_DIRECTORY),
+        &Language::Tagalog => Some(TAGALOG_TESTDATA_DIRECTORY),
+        &Language::Tamil => Some(TAMIL_TESTDATA_DIRECTORY),
+        &Language::Telugu => Some(TELUGU_TESTDATA_DIRECTORY),
+        &Language::Thai => Some(THAI_TESTDATA_DIRECTORY),
+        &Language::Tsonga => Some(TSONGA_TESTDATA_DIRECTORY),
+        &Language::Tswana => Some(TSWANA_TESTDATA_DIRECTORY),
+        &Language::Turkish => Some(TURKISH_TESTDATA_DIRECTORY),
+        &Language::Ukrainian => Some(UKRAINIAN_TESTDATA_DIRECTORY),
+        &Language::Urdu => Some(URDU_TESTDATA_DIRECTORY),
+        &Language::Vietnamese => Some(VIETNAMESE_TESTDATA_DIRECTORY),
+        &Language::Welsh => Some(WELSH_TESTDATA_DIRECTORY),
+        &Language::Xhosa => Some(XHOSA_TESTDATA_DIRECTORY),
+        &Language::Yoruba => Some(YORUBA_TESTDATA_DIRECTORY),
+        &Language::Zulu => Some(ZULU_TESTDATA_DIRECTORY),
+
+        // These languages are detected by their unique alphabets alone
+        // and have no test data.
+        &Language::Amharic
+        | &Language::Burmese
+        | &Language::Dhivehi
+        | &Language::Kannada
+        | &Language::Khmer
+        | &Language::Lao
+        | &Language::Malayalam
+        | &Language::Odia
+        | &Language::Sinhala
+        | &Language::Tibetan => None,
     }
 }
@@ -0,0 +1,199 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Fits the slope of the logistic function which maps the margin computed by
+//! `LanguageDetector::verify_language` to a score, using Platt scaling.
+//!
+//! The test data of every language is used, which none of the bundled models have been trained
+//! on. Every text contributes a positive example, the verification of its true language, and a
+//! negative one, the verification of the strongest competitor of its true language. The texts on
+//! even lines are used for fitting, the ones on odd lines for measuring the calibration.
+
+use itertools::Itertools;
+use lingua::{Language, LanguageDetector, LanguageDetectorBuilder};
+use std::fs;
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+
+const TEST_DATA_FILE_NAMES: [&str; 3] = ["single-words.txt", "word-pairs.txt", "sentences.txt"];
+const RELIABILITY_BIN_COUNT: usize = 10;
+
+fn main() {
+    let detector = LanguageDetectorBuilder::from_all_languages().build();
+    let mut fitting_examples = vec![];
+    let mut evaluation_examples = vec![];
+
+    let languages_with_test_data = Language::iter()
+        .filter(|it| get_test_data_directory(it).is_dir())
+        .collect_vec();
+
+    for language in languages_with_test_data {
+        println!("Collecting margins for {:?}...", &language);
+
+        for file_name in TEST_DATA_FILE_NAMES.iter() {
+            for (idx, text) in read_test_data(&language, file_name).iter().enumerate() {
+                let examples = if idx % 2 == 0 {
+                    &mut fitting_examples
+                } else {
+                    &mut evaluation_examples
+                };
+                collect_examples(&detector, text, &language, examples);
+            }
+        }
+    }
+
+    let slope = fit_slope(&fitting_examples);
+
+    println!();
+    println!(
+        "Fitted on {} and evaluated on {} examples",
+        fitting_examples.len(),
+        evaluation_examples.len()
+    );
+    println!("VERIFICATION_SCORE_SLOPE: f64 = {}", slope);
+    println!();
+
+    print_reliability("uncalibrated", &evaluation_examples, 1.0);
+    print_reliability("calibrated", &evaluation_examples, slope);
+}
+
+fn get_test_data_directory(language: &Language) -> PathBuf {
+    Path::new("language-models")
+        .join(language.iso_code_639_1().to_string())
+        .join("testdata")
+}
+
+fn read_test_data(language: &Language, file_name: &str) -> Vec<String> {
+    let file_path = get_test_data_directory(language).join(file_name);
+
+    fs::read_to_string(&file_path)
+        .unwrap_or_else(|_| panic!("{} could not be read", file_path.display()))
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect_vec()
+}
+
+fn collect_examples(
+    detector: &LanguageDetector,
+    text: &str,
+    language: &Language,
+    examples: &mut Vec<(f64, bool)>,
+) {
+    let verification = detector.verify_language(text, language.clone());
+
+    if let Some(margin) = verification.margin() {
+        examples.push((margin, true));
+    }
+
+    if let Some(competitor) = verification.strongest_competitor() {
+        let competing_verification = detector.verify_language(text, competitor.clone());
+
+        if let Some(margin) = competing_verification.margin() {
+            examples.push((margin, false));
+        }
+    }
+}
+
+/// Fits the slope of the logistic function by Newton's method, using the regularized target
+/// values suggested by Platt (1999) to avoid overfitting. The logistic function is not shifted,
+/// so that the score stays 0.5 if both languages are equally likely.
+fn fit_slope(examples: &[(f64, bool)]) -> f64 {
+    let positive_count = examples
+        .iter()
+        .filter(|(_, is_positive)| *is_positive)
+        .count() as f64;
+    let negative_count = examples.len() as f64 - positive_count;
+    let high_target = (positive_count + 1.0) / (positive_count + 2.0);
+    let low_target = 1.0 / (negative_count + 2.0);
+    let mut slope = 0.1;
+
+    for _ in 0..100 {
+        let mut gradient = 0.0;
+        let mut hessian = 1e-12;
+
+        for (margin, is_positive) in examples.iter() {
+            let target = if *is_positive {
+                high_target
+            } else {
+                low_target
+            };
+            let score = logistic(slope * margin);
+
+            gradient += (score - target) * margin;
+            hessian += score * (1.0 - score) * margin * margin;
+        }
+
+        let step = gradient / hessian;
+        slope -= step;
+
+        if step.abs() < 1e-9 {
+            break;
+        }
+    }
+
+    slope
+}
+
+fn logistic(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn print_reliability(description: &str, examples: &[(f64, bool)], slope: f64) {
+    let mut bins = vec![(0usize, 0.0, 0usize); RELIABILITY_BIN_COUNT];
+    let mut brier_score = 0.0;
+
+    for (margin, is_positive) in examples.iter() {
+        let score = logistic(slope * margin);
+        let outcome = if *is_positive { 1.0 } else { 0.0 };
+        let bin = &mut bins
+            [((score * RELIABILITY_BIN_COUNT as f64) as usize).min(RELIABILITY_BIN_COUNT - 1)];
+
+        bin.0 += 1;
+        bin.1 += score;
+        bin.2 += *is_positive as usize;
+        brier_score += (score - outcome).powi(2);
+    }
+
+    let expected_calibration_error = bins
+        .iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(_, score_sum, positive_count)| {
+            (score_sum - *positive_count as f64).abs() / examples.len() as f64
+        })
+        .sum::<f64>();
+
+    println!("{} scores:", description);
+    println!("  Brier score: {:.4}", brier_score / examples.len() as f64);
+    println!(
+        "  Expected calibration error: {:.4}",
+        expected_calibration_error
+    );
+
+    for (idx, (count, score_sum, positive_count)) in bins.iter().enumerate() {
+        if *count > 0 {
+            println!(
+                "  {:.1}-{:.1}: mean score {:.3}, observed {:.3} ({} examples)",
+                idx as f64 / RELIABILITY_BIN_COUNT as f64,
+                (idx + 1) as f64 / RELIABILITY_BIN_COUNT as f64,
+                score_sum / *count as f64,
+                *positive_count as f64 / *count as f64,
+                count
+            );
+        }
+    }
+    println!();
+}
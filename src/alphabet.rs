@@ -21,13 +21,15 @@ use std::collections::HashMap;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-#[derive(EnumIter, Eq, PartialEq, Hash)]
-pub(crate) enum Alphabet {
+/// This enum specifies the scripts that the supported languages are written in.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Alphabet {
     Arabic,
     Armenian,
     Bengali,
     Cyrillic,
     Devanagari,
+    Ethiopic,
     Georgian,
     Greek,
     Gujarati,
@@ -36,21 +38,65 @@ pub(crate) enum Alphabet {
     Hangul,
     Hebrew,
     Hiragana,
+    Kannada,
     Katakana,
+    Khmer,
+    Lao,
     Latin,
+    Malayalam,
+    Myanmar,
+    Oriya,
+    Sinhala,
     Tamil,
     Telugu,
+    Thaana,
     Thai,
+    Tibetan,
 }
 
 impl Alphabet {
-    pub fn matches(&self, text: &str) -> bool {
+    /// Returns the four-letter ISO 15924 code of this script, such as `Latn` or `Cyrl`.
+    pub fn iso_code_15924(&self) -> &'static str {
+        match self {
+            Alphabet::Arabic => "Arab",
+            Alphabet::Armenian => "Armn",
+            Alphabet::Bengali => "Beng",
+            Alphabet::Cyrillic => "Cyrl",
+            Alphabet::Devanagari => "Deva",
+            Alphabet::Ethiopic => "Ethi",
+            Alphabet::Georgian => "Geor",
+            Alphabet::Greek => "Grek",
+            Alphabet::Gujarati => "Gujr",
+            Alphabet::Gurmukhi => "Guru",
+            Alphabet::Han => "Hani",
+            Alphabet::Hangul => "Hang",
+            Alphabet::Hebrew => "Hebr",
+            Alphabet::Hiragana => "Hira",
+            Alphabet::Kannada => "Knda",
+            Alphabet::Katakana => "Kana",
+            Alphabet::Khmer => "Khmr",
+            Alphabet::Lao => "Laoo",
+            Alphabet::Latin => "Latn",
+            Alphabet::Malayalam => "Mlym",
+            Alphabet::Myanmar => "Mymr",
+            Alphabet::Oriya => "Orya",
+            Alphabet::Sinhala => "Sinh",
+            Alphabet::Tamil => "Taml",
+            Alphabet::Telugu => "Telu",
+            Alphabet::Thaana => "Thaa",
+            Alphabet::Thai => "Thai",
+            Alphabet::Tibetan => "Tibt",
+        }
+    }
+
+    pub(crate) fn matches(&self, text: &str) -> bool {
         match self {
             Alphabet::Arabic => ARABIC.is_match(text),
             Alphabet::Armenian => ARMENIAN.is_match(text),
             Alphabet::Bengali => BENGALI.is_match(text),
             Alphabet::Cyrillic => CYRILLIC.is_match(text),
             Alphabet::Devanagari => DEVANAGARI.is_match(text),
+            Alphabet::Ethiopic => ETHIOPIC.is_match(text),
             Alphabet::Georgian => GEORGIAN.is_match(text),
             Alphabet::Greek => GREEK.is_match(text),
             Alphabet::Gujarati => GUJARATI.is_match(text),
@@ -59,15 +105,24 @@ impl Alphabet {
             Alphabet::Hangul => HANGUL.is_match(text),
             Alphabet::Hebrew => HEBREW.is_match(text),
             Alphabet::Hiragana => HIRAGANA.is_match(text),
+            Alphabet::Kannada => KANNADA.is_match(text),
             Alphabet::Katakana => KATAKANA.is_match(text),
+            Alphabet::Khmer => KHMER.is_match(text),
+            Alphabet::Lao => LAO.is_match(text),
             Alphabet::Latin => LATIN.is_match(text),
+            Alphabet::Malayalam => MALAYALAM.is_match(text),
+            Alphabet::Myanmar => MYANMAR.is_match(text),
+            Alphabet::Oriya => ORIYA.is_match(text),
+            Alphabet::Sinhala => SINHALA.is_match(text),
             Alphabet::Tamil => TAMIL.is_match(text),
             Alphabet::Telugu => TELUGU.is_match(text),
+            Alphabet::Thaana => THAANA.is_match(text),
             Alphabet::Thai => THAI.is_match(text),
+            Alphabet::Tibetan => TIBETAN.is_match(text),
         }
     }
 
-    pub fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
+    pub(crate) fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
         let mut alphabets = HashMap::new();
         for alphabet in Alphabet::iter() {
             let supported_languages = alphabet.supported_languages();
@@ -89,11 +144,32 @@ impl Alphabet {
     }
 }
 
+/// This enum specifies variants of a script which are distinguished for some languages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ScriptVariant {
+    /// Simplified Han characters, as used in mainland China and Singapore.
+    Simplified,
+
+    /// Traditional Han characters, as used in Taiwan, Hong Kong and Macau.
+    Traditional,
+}
+
+impl ScriptVariant {
+    /// Returns the four-letter ISO 15924 code of this script variant, such as `Hans` or `Hant`.
+    pub fn iso_code_15924(&self) -> &'static str {
+        match self {
+            ScriptVariant::Simplified => "Hans",
+            ScriptVariant::Traditional => "Hant",
+        }
+    }
+}
+
 static ARABIC: Lazy<Regex> = Lazy::new(|| create_regex("Arabic"));
 static ARMENIAN: Lazy<Regex> = Lazy::new(|| create_regex("Armenian"));
 static BENGALI: Lazy<Regex> = Lazy::new(|| create_regex("Bengali"));
 static CYRILLIC: Lazy<Regex> = Lazy::new(|| create_regex("Cyrillic"));
 static DEVANAGARI: Lazy<Regex> = Lazy::new(|| create_regex("Devanagari"));
+static ETHIOPIC: Lazy<Regex> = Lazy::new(|| create_regex("Ethiopic"));
 static GEORGIAN: Lazy<Regex> = Lazy::new(|| create_regex("Georgian"));
 static GREEK: Lazy<Regex> = Lazy::new(|| create_regex("Greek"));
 static GUJARATI: Lazy<Regex> = Lazy::new(|| create_regex("Gujarati"));
@@ -102,11 +178,20 @@ static HAN: Lazy<Regex> = Lazy::new(|| create_regex("Han"));
 static HANGUL: Lazy<Regex> = Lazy::new(|| create_regex("Hangul"));
 static HEBREW: Lazy<Regex> = Lazy::new(|| create_regex("Hebrew"));
 static HIRAGANA: Lazy<Regex> = Lazy::new(|| create_regex("Hiragana"));
+static KANNADA: Lazy<Regex> = Lazy::new(|| create_regex("Kannada"));
 static KATAKANA: Lazy<Regex> = Lazy::new(|| create_regex("Katakana"));
+static KHMER: Lazy<Regex> = Lazy::new(|| create_regex("Khmer"));
+static LAO: Lazy<Regex> = Lazy::new(|| create_regex("Lao"));
 static LATIN: Lazy<Regex> = Lazy::new(|| create_regex("Latin"));
+static MALAYALAM: Lazy<Regex> = Lazy::new(|| create_regex("Malayalam"));
+static MYANMAR: Lazy<Regex> = Lazy::new(|| create_regex("Myanmar"));
+static ORIYA: Lazy<Regex> = Lazy::new(|| create_regex("Oriya"));
+static SINHALA: Lazy<Regex> = Lazy::new(|| create_regex("Sinhala"));
 static TAMIL: Lazy<Regex> = Lazy::new(|| create_regex("Tamil"));
 static TELUGU: Lazy<Regex> = Lazy::new(|| create_regex("Telugu"));
+static THAANA: Lazy<Regex> = Lazy::new(|| create_regex("Thaana"));
 static THAI: Lazy<Regex> = Lazy::new(|| create_regex("Thai"));
+static TIBETAN: Lazy<Regex> = Lazy::new(|| create_regex("Tibetan"));
 
 fn create_regex(char_class: &str) -> Regex {
     Regex::new(&format!("^\\p{{{}}}+$", char_class)).unwrap()
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
+use crate::cache::ResultCache;
 use crate::detector::LanguageDetector;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 use crate::language::Language;
+use crate::preprocessor::{default_text_preprocessors, TextPreprocessor};
+use crate::sampling::SamplingStrategy;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 const MISSING_LANGUAGE_MESSAGE: &str = "LanguageDetector needs at least 2 languages to choose from";
 
@@ -26,6 +30,17 @@ const MISSING_LANGUAGE_MESSAGE: &str = "LanguageDetector needs at least 2 langua
 pub struct LanguageDetectorBuilder {
     languages: HashSet<Language>,
     minimum_relative_distance: f64,
+    use_folded_language_models: bool,
+    include_romanized_languages: bool,
+    correct_keyboard_layouts: bool,
+    normalize_homoglyphs: bool,
+    text_preprocessors: Vec<Arc<dyn TextPreprocessor>>,
+    down_weight_proper_nouns: bool,
+    weight_ngrams_by_count: bool,
+    sampling_strategy: Option<SamplingStrategy>,
+    check_sample_consistency: bool,
+    pruning_margin: Option<f64>,
+    cache: Option<Arc<ResultCache>>,
 }
 
 impl LanguageDetectorBuilder {
@@ -146,16 +161,242 @@ impl LanguageDetectorBuilder {
         self
     }
 
+    /// Enables the diacritic-insensitive detection of text typed without accents.
+    ///
+    /// Texts written on keyboards without the respective layout often lack
+    /// the diacritics which their language normally uses, e.g. *cafe* instead
+    /// of *café*. If this option is enabled and the input text does not
+    /// contain any diacritics at all, the ngrams are looked up in folded
+    /// variants of the language models. These are derived from the regular
+    /// models by stripping the diacritics from all ngrams and merging the
+    /// frequencies in the training data of ngrams which become equal in doing so.
+    ///
+    /// The folded models are computed lazily the first time they are needed.
+    pub fn with_folded_language_models(&mut self) -> &mut Self {
+        self.use_folded_language_models = true;
+        self
+    }
+
+    /// Enables the detection of languages which are written in Latin script
+    /// although their native script is a different one.
+    ///
+    /// Russian, Ukrainian, Bulgarian, Greek, Hindi and Marathi are frequently
+    /// typed in Latin letters online, e.g. *privet kak dela* instead of
+    /// *привет как дела*. If this option is enabled, Latin input text is
+    /// additionally converted into the native scripts of these languages
+    /// and then scored with their regular language models. Only languages
+    /// in which the converted text is about as plausible as the original text
+    /// is in any language written in Latin script are considered. A language
+    /// detected in this way is marked as romanized in the
+    /// [`DetectionResult`](./struct.DetectionResult.html).
+    pub fn with_romanized_languages(&mut self) -> &mut Self {
+        self.include_romanized_languages = true;
+        self
+    }
+
+    /// Enables the correction of text which has been typed with the wrong keyboard layout.
+    ///
+    /// Search queries such as *ghbdtn* are often Russian *привет* typed on
+    /// a US keyboard layout. If this option is enabled, Latin input text is
+    /// additionally remapped through the Russian, Ukrainian, Bulgarian, Greek,
+    /// Hebrew and Arabic keyboard layouts and scored with the respective
+    /// language models. If the remapped text turns out to be far more
+    /// plausible than the original one, the language is detected from the
+    /// remapped text, which is then available as the corrected text of the
+    /// [`DetectionResult`](./struct.DetectionResult.html).
+    pub fn with_keyboard_layout_correction(&mut self) -> &mut Self {
+        self.correct_keyboard_layouts = true;
+        self
+    }
+
+    /// Enables the normalization of words which mix look-alike letters of different scripts.
+    ///
+    /// Spam and evasion texts often replace single letters of a word with
+    /// letters of another script which look the same, such as the Cyrillic
+    /// *а* in *pаypаl*. If this option is enabled, the look-alike letters of
+    /// Latin, Cyrillic and Greek script within such mixed-script words are
+    /// replaced with the letters of the script which dominates the input text
+    /// before its language is detected. Whether this happened is reported by
+    /// the [`DetectionResult`](./struct.DetectionResult.html).
+    pub fn with_homoglyph_normalization(&mut self) -> &mut Self {
+        self.normalize_homoglyphs = true;
+        self
+    }
+
+    /// Registers the default chain of text preprocessors which remove code snippets,
+    /// URLs, email addresses, mentions, hashtags, emoji and other symbols from the
+    /// input text before its language is detected.
+    ///
+    /// These are the filters [`InlineCodeFilter`], [`UrlFilter`], [`EmailAddressFilter`],
+    /// [`MentionFilter`], [`HashtagFilter`] and [`EmojiFilter`], applied in this order.
+    /// Further preprocessors can be registered with
+    /// [`with_text_preprocessor`](#method.with_text_preprocessor).
+    ///
+    /// [`InlineCodeFilter`]: ./struct.InlineCodeFilter.html
+    /// [`UrlFilter`]: ./struct.UrlFilter.html
+    /// [`EmailAddressFilter`]: ./struct.EmailAddressFilter.html
+    /// [`MentionFilter`]: ./struct.MentionFilter.html
+    /// [`HashtagFilter`]: ./struct.HashtagFilter.html
+    /// [`EmojiFilter`]: ./struct.EmojiFilter.html
+    pub fn with_default_text_preprocessors(&mut self) -> &mut Self {
+        self.text_preprocessors
+            .append(&mut default_text_preprocessors());
+        self
+    }
+
+    /// Registers a text preprocessor which is applied to the input text before its language
+    /// is detected, after all preprocessors which have been registered before.
+    pub fn with_text_preprocessor<P: TextPreprocessor + 'static>(
+        &mut self,
+        preprocessor: P,
+    ) -> &mut Self {
+        self.text_preprocessors.push(Arc::new(preprocessor));
+        self
+    }
+
+    /// Reduces the weight of words which are most likely proper nouns.
+    ///
+    /// Names of people, brands and places are no evidence for the language of the
+    /// surrounding text, such as *Washington Post* in a German sentence. If this option
+    /// is enabled, acronyms, CamelCase words and capitalized words which do not start
+    /// a sentence contribute less to the ngram probabilities of the input text. These words
+    /// are reported by the [`DetectionResult`](./struct.DetectionResult.html).
+    ///
+    /// Capitalized words which the word models of the languages to detect know, such as
+    /// German common nouns, are only down-weighted if they directly follow or precede
+    /// another capitalized word within the same sentence.
+    pub fn with_proper_noun_down_weighting(&mut self) -> &mut Self {
+        self.down_weight_proper_nouns = true;
+        self
+    }
+
+    /// Weights the logarithmized probability of each ngram of the input text
+    /// by the number of its occurrences.
+    ///
+    /// By default, each distinct ngram contributes once, no matter how often it occurs.
+    /// If this option is enabled, the multinomial likelihood of the input text is computed
+    /// instead, which separates closely related languages better in longer texts.
+    pub fn with_ngram_count_weighting(&mut self) -> &mut Self {
+        self.weight_ngrams_by_count = true;
+        self
+    }
+
+    /// Detects the language of long input texts from a representative sample
+    /// which is taken according to the given [`SamplingStrategy`](./enum.SamplingStrategy.html).
+    ///
+    /// By default, the whole input text is analyzed, which takes seconds for documents of
+    /// several megabytes. Texts which are not longer than the sample are analyzed completely.
+    ///
+    /// ⚠ Panics if the window count or the window length is 0.
+    pub fn with_sampling(&mut self, strategy: SamplingStrategy) -> &mut Self {
+        let (window_count, window_length) = strategy.windows();
+        if window_count == 0 {
+            panic!("sampling window count must be greater than 0");
+        }
+        if window_length == 0 {
+            panic!("sampling window length must be greater than 0");
+        }
+        self.sampling_strategy = Some(strategy);
+        self
+    }
+
+    /// Detects the language of input texts from their first `length` characters only.
+    /// This is a shorthand for [`with_sampling`](#method.with_sampling) and
+    /// [`SamplingStrategy::FirstCharacters`](./enum.SamplingStrategy.html#variant.FirstCharacters).
+    ///
+    /// ⚠ Panics if the length is 0.
+    pub fn with_max_text_length(&mut self, length: usize) -> &mut Self {
+        self.with_sampling(SamplingStrategy::FirstCharacters(length))
+    }
+
+    /// Detects the language of each window of a sample separately in addition to the whole
+    /// sample and reports how many windows agree with the detected language.
+    ///
+    /// The share of agreeing windows is available from the
+    /// [`DetectionResult`](./struct.DetectionResult.html). It is only computed for sampling
+    /// strategies which take several windows and slows down the detection accordingly.
+    pub fn with_sample_consistency_check(&mut self) -> &mut Self {
+        self.check_sample_consistency = true;
+        self
+    }
+
+    /// Enables the adaptive mode which stops scoring languages that have fallen
+    /// far behind the most likely language.
+    ///
+    /// By default, every candidate language is scored against all ngrams of the
+    /// input text, from unigrams up to fivegrams. In adaptive mode, the summed up
+    /// probabilities of the candidates are compared after each chunk of ngrams,
+    /// starting with the bigrams.
+    /// A candidate is dropped as soon as the ratio of the most likely language's
+    /// probabilities to its own falls below `1.0 - margin`. Dropped languages are
+    /// not part of the computed confidence values. Languages within the margin
+    /// are scored completely, so close decisions are not affected.
+    ///
+    /// ⚠ Panics if `margin` is not greater than 0.0 and smaller than 1.0.
+    pub fn with_adaptive_pruning(&mut self, margin: f64) -> &mut Self {
+        if margin <= 0.0 || margin >= 1.0 {
+            panic!("pruning margin must lie in between 0.0 and 1.0 exclusively");
+        }
+        self.pruning_margin = Some(margin);
+        self
+    }
+
+    /// Caches the confidence values of up to `capacity` input texts, keyed on the text after
+    /// it has been cleaned up, and evicts the least recently used one when the cache is full.
+    ///
+    /// This pays off for short texts which occur repeatedly, such as user interface strings.
+    /// The cache is thread-safe and shared by all detectors built from this builder afterwards.
+    /// Their results never mix up because every entry is bound to the options that the
+    /// detector storing it has been built with, including the minimum relative distance and
+    /// the [`TextPreprocessor::id`](./trait.TextPreprocessor.html#method.id) of every text
+    /// preprocessor. If a preprocessor has no id, the detector does not use the cache at all.
+    /// Hit and miss statistics are available from
+    /// [`LanguageDetector::cache_statistics`](./struct.LanguageDetector.html#method.cache_statistics).
+    ///
+    /// ⚠ Panics if the capacity is 0.
+    pub fn with_cache(&mut self, capacity: usize) -> &mut Self {
+        if capacity == 0 {
+            panic!("cache capacity must be greater than 0");
+        }
+        self.cache = Some(Arc::new(ResultCache::new(capacity)));
+        self
+    }
+
     /// Creates and returns the configured instance of
     /// [`LanguageDetector`](./struct.LanguageDetector.html).
     pub fn build(&mut self) -> LanguageDetector {
-        LanguageDetector::from(self.languages.clone(), self.minimum_relative_distance)
+        LanguageDetector::from(
+            self.languages.clone(),
+            self.minimum_relative_distance,
+            self.use_folded_language_models,
+            self.include_romanized_languages,
+            self.correct_keyboard_layouts,
+            self.normalize_homoglyphs,
+            self.text_preprocessors.clone(),
+            self.down_weight_proper_nouns,
+            self.weight_ngrams_by_count,
+            self.sampling_strategy,
+            self.check_sample_consistency,
+            self.pruning_margin,
+            self.cache.clone(),
+        )
     }
 
     fn from(languages: HashSet<Language>) -> Self {
         Self {
             languages,
             minimum_relative_distance: 0.0,
+            use_folded_language_models: false,
+            include_romanized_languages: false,
+            correct_keyboard_layouts: false,
+            normalize_homoglyphs: false,
+            text_preprocessors: vec![],
+            down_weight_proper_nouns: false,
+            weight_ngrams_by_count: false,
+            sampling_strategy: None,
+            check_sample_consistency: false,
+            pruning_margin: None,
+            cache: None,
         }
     }
 }
@@ -163,15 +404,76 @@ impl LanguageDetectorBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::preprocessor::UrlFilter;
 
     #[test]
     fn assert_detector_can_be_built_from_all_languages() {
         let mut builder = LanguageDetectorBuilder::from_all_languages();
         assert_eq!(builder.languages, Language::all());
         assert_eq!(builder.minimum_relative_distance, 0.0);
+        assert!(!builder.use_folded_language_models);
+        assert!(!builder.include_romanized_languages);
+        assert!(!builder.correct_keyboard_layouts);
+        assert!(!builder.normalize_homoglyphs);
+        assert!(builder.text_preprocessors.is_empty());
+        assert!(!builder.down_weight_proper_nouns);
+        assert!(!builder.weight_ngrams_by_count);
+        assert_eq!(builder.sampling_strategy, None);
+        assert!(!builder.check_sample_consistency);
+        assert_eq!(builder.pruning_margin, None);
+        assert!(builder.cache.is_none());
 
         builder.with_minimum_relative_distance(0.2);
         assert_eq!(builder.minimum_relative_distance, 0.2);
+
+        builder.with_folded_language_models();
+        assert!(builder.use_folded_language_models);
+
+        builder.with_romanized_languages();
+        assert!(builder.include_romanized_languages);
+
+        builder.with_keyboard_layout_correction();
+        assert!(builder.correct_keyboard_layouts);
+
+        builder.with_homoglyph_normalization();
+        assert!(builder.normalize_homoglyphs);
+
+        builder.with_default_text_preprocessors();
+        assert_eq!(builder.text_preprocessors.len(), 6);
+
+        builder.with_text_preprocessor(UrlFilter);
+        assert_eq!(builder.text_preprocessors.len(), 7);
+
+        builder.with_proper_noun_down_weighting();
+        assert!(builder.down_weight_proper_nouns);
+
+        builder.with_ngram_count_weighting();
+        assert!(builder.weight_ngrams_by_count);
+
+        builder.with_max_text_length(1000);
+        assert_eq!(
+            builder.sampling_strategy,
+            Some(SamplingStrategy::FirstCharacters(1000))
+        );
+
+        let strategy = SamplingStrategy::EvenlySpacedWindows {
+            window_count: 5,
+            window_length: 200,
+        };
+        builder.with_sampling(strategy);
+        assert_eq!(builder.sampling_strategy, Some(strategy));
+
+        builder.with_sample_consistency_check();
+        assert!(builder.check_sample_consistency);
+
+        builder.with_adaptive_pruning(0.5);
+        assert_eq!(builder.pruning_margin, Some(0.5));
+
+        builder.with_cache(100);
+        assert_eq!(
+            builder.cache.as_ref().map(|it| it.statistics().capacity()),
+            Some(100)
+        );
     }
 
     #[test]
@@ -244,6 +546,47 @@ mod tests {
         );
     }
 
+    #[test]
+    #[should_panic(expected = "pruning margin must lie in between 0.0 and 1.0 exclusively")]
+    fn assert_pruning_margin_must_not_be_too_large() {
+        LanguageDetectorBuilder::from_all_languages().with_adaptive_pruning(1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "sampling window count must be greater than 0")]
+    fn assert_sampling_window_count_must_not_be_zero() {
+        LanguageDetectorBuilder::from_all_languages().with_sampling(
+            SamplingStrategy::EvenlySpacedWindows {
+                window_count: 0,
+                window_length: 100,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sampling window length must be greater than 0")]
+    fn assert_sampling_window_length_must_not_be_zero() {
+        LanguageDetectorBuilder::from_all_languages().with_sampling(
+            SamplingStrategy::RandomWindows {
+                window_count: 3,
+                window_length: 0,
+                seed: 42,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sampling window length must be greater than 0")]
+    fn assert_max_text_length_must_not_be_zero() {
+        LanguageDetectorBuilder::from_all_languages().with_max_text_length(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cache capacity must be greater than 0")]
+    fn assert_cache_must_not_be_empty() {
+        LanguageDetectorBuilder::from_all_languages().with_cache(0);
+    }
+
     #[test]
     #[should_panic(expected = "LanguageDetector needs at least 2 languages to choose from")]
     fn assert_detector_cannot_be_built_from_too_short_whitelist() {
@@ -0,0 +1,218 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// This struct reports how often the result cache of a
+/// [`LanguageDetector`](./struct.LanguageDetector.html) has been used.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CacheStatistics {
+    hits: usize,
+    misses: usize,
+    len: usize,
+    capacity: usize,
+}
+
+impl CacheStatistics {
+    /// Returns the number of lookups which have been answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of lookups which have required a new detection.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the number of results which are currently stored in the cache.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the cache does not store any results.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of results which the cache stores.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the share of lookups, between 0.0 and 1.0, which have been answered
+    /// from the cache, or 0.0 if there have not been any lookups yet.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// The key of a cached result. Besides the cleaned up input text, it contains everything
+/// else which the confidence values depend on, so that detectors with different options
+/// can share the same cache.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct CacheKey {
+    pub(crate) options_fingerprint: u64,
+    pub(crate) cleaned_up_text: String,
+    pub(crate) down_weighted_words: Vec<String>,
+}
+
+/// A thread-safe cache of confidence values which evicts the least recently used
+/// entry once its capacity is exceeded.
+pub(crate) struct ResultCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    values: HashMap<CacheKey, (u64, Vec<(Language, f64)>)>,
+    usage: BTreeMap<u64, CacheKey>,
+    last_usage: u64,
+}
+
+impl ResultCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(CacheEntries::default()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<Vec<(Language, f64)>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.last_usage += 1;
+        let last_usage = entries.last_usage;
+
+        let (previous_usage, confidence_values) = match entries.values.get_mut(key) {
+            Some((usage, confidence_values)) => {
+                let previous_usage = *usage;
+                *usage = last_usage;
+                (previous_usage, confidence_values.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+
+        entries.usage.remove(&previous_usage);
+        entries.usage.insert(last_usage, key.clone());
+        self.hits.fetch_add(1, Ordering::Relaxed);
+
+        Some(confidence_values)
+    }
+
+    pub(crate) fn insert(&self, key: CacheKey, confidence_values: Vec<(Language, f64)>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.last_usage += 1;
+        let last_usage = entries.last_usage;
+
+        if let Some((previous_usage, _)) = entries
+            .values
+            .insert(key.clone(), (last_usage, confidence_values))
+        {
+            entries.usage.remove(&previous_usage);
+        }
+        entries.usage.insert(last_usage, key);
+
+        while entries.values.len() > self.capacity {
+            let least_recent_usage = *entries.usage.keys().next().unwrap();
+            let least_recent_key = entries.usage.remove(&least_recent_usage).unwrap();
+            entries.values.remove(&least_recent_key);
+        }
+    }
+
+    pub(crate) fn statistics(&self) -> CacheStatistics {
+        CacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: self.entries.lock().unwrap().values.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, German};
+
+    fn key(text: &str) -> CacheKey {
+        CacheKey {
+            options_fingerprint: 0,
+            cleaned_up_text: text.to_string(),
+            down_weighted_words: vec![],
+        }
+    }
+
+    #[test]
+    fn assert_least_recently_used_entry_is_evicted() {
+        let cache = ResultCache::new(2);
+
+        cache.insert(key("alter"), vec![(German, 1.0)]);
+        cache.insert(key("after"), vec![(English, 1.0)]);
+        assert_eq!(cache.get(&key("alter")), Some(vec![(German, 1.0)]));
+
+        cache.insert(key("water"), vec![(English, 1.0)]);
+
+        assert_eq!(cache.get(&key("after")), None);
+        assert_eq!(cache.get(&key("alter")), Some(vec![(German, 1.0)]));
+        assert_eq!(cache.get(&key("water")), Some(vec![(English, 1.0)]));
+    }
+
+    #[test]
+    fn assert_statistics_are_counted() {
+        let cache = ResultCache::new(10);
+
+        assert_eq!(cache.get(&key("alter")), None);
+        cache.insert(key("alter"), vec![(German, 1.0)]);
+        cache.get(&key("alter"));
+        cache.get(&key("alter"));
+
+        let statistics = cache.statistics();
+
+        assert_eq!(statistics.hits(), 2);
+        assert_eq!(statistics.misses(), 1);
+        assert_eq!(statistics.len(), 1);
+        assert_eq!(statistics.capacity(), 10);
+        assert!((statistics.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn assert_keys_with_different_options_do_not_collide() {
+        let cache = ResultCache::new(10);
+        let other_key = CacheKey {
+            options_fingerprint: 1,
+            ..key("alter")
+        };
+
+        cache.insert(key("alter"), vec![(German, 1.0)]);
+
+        assert_eq!(cache.get(&other_key), None);
+    }
+}
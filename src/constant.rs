@@ -14,12 +14,14 @@
  * limitations under the License.
  */
 
+use crate::alphabet::Alphabet;
 use crate::language::Language;
 use crate::language::Language::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
+pub(crate) static FINAL_SIGMA: Lazy<Regex> = Lazy::new(|| Regex::new("σ\\b").unwrap());
 pub(crate) static JAPANESE_CHARACTER_SET: Lazy<Regex> =
     Lazy::new(|| Regex::new("^[\\p{Hiragana}\\p{Katakana}\\p{Han}]+$").unwrap());
 pub(crate) static LETTER: Lazy<Regex> = Lazy::new(|| Regex::new("^\\p{L}+$").unwrap());
@@ -28,9 +30,68 @@ pub(crate) static NO_LETTER: Lazy<Regex> = Lazy::new(|| Regex::new("^[^\\p{L}]+$
 pub(crate) static NUMBERS: Lazy<Regex> = Lazy::new(|| Regex::new("\\p{N}").unwrap());
 pub(crate) static PUNCTUATION: Lazy<Regex> = Lazy::new(|| Regex::new("\\p{P}").unwrap());
 
-pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet<Language>>> =
-    Lazy::new(|| {
-        hashmap!(
+/// Frequent Han characters which are used in Simplified Chinese only.
+pub(crate) static SIMPLIFIED_CHINESE_CHARACTERS: Lazy<HashSet<char>> = Lazy::new(|| {
+    [
+        "与业东个为丽举么义习乡书买争产亲从们价会体兰关兴养写军农击办务动区医华卖卫历压双",
+        "发变号听员团园国图圣场声处头学实对导尽层岁师广应开张归当录总戏战护报无旧时术机杀",
+        "杂权条来极构标样检欢欧气汉济湾满灵点爱状独环现电画监确礼离种稳笔类红约级纪线练组",
+        "细织终经结给绝统续绿网罗职联脑艺节药获虽补装见观规视览觉计认让讲许论设证识试诗话",
+        "该语误说请诸读课调谈谢贝负财责货质购贵费资赛赶车轻较输边达过运还这进远违连迟适选",
+        "逻邮钟钢钱铁银错镜长门问间闻阅队阳阵阶际陆险随难韩页顺须顾领频题风飞饭饮馆马验鱼",
+        "鸟鸡黄齐齿龙龟",
+    ]
+    .concat()
+    .chars()
+    .collect()
+});
+
+/// The Traditional Chinese counterparts of `SIMPLIFIED_CHINESE_CHARACTERS`.
+pub(crate) static TRADITIONAL_CHINESE_CHARACTERS: Lazy<HashSet<char>> = Lazy::new(|| {
+    [
+        "來個們價動務區員問國園圖團報場壓學實寫對導層師廣張從愛應戰戲擊時書會東條業極構標",
+        "樣機檢權歐歡歲歷歸殺氣滿漢濟灣為無爭爲狀獨獲現環產畫當發盡監確禮種穩筆節紀約紅級",
+        "細終組結絕給統經綠網線練總織續羅義習聖聞聯聲職聽腦與興舉舊華藝藥蘭處號術衛補裝見",
+        "規視親覺覽觀計設許試詩話該認語誤說課調談請論諸講謝證識護讀變讓貝負財貨責貴買費資",
+        "賣質購賽趕車軍較輕輸辦農這連進運過達違遠適遲選還邊邏郵鄉醫銀鋼錄錢錯鏡鐘鐵長門開",
+        "間閱關陣陸陽隊階際隨險雖雙雜雞離難電靈韓頁順須領頭頻題類顧風飛飯飲養館馬驗體魚鳥",
+        "麗麼黃點齊齒龍龜",
+    ]
+    .concat()
+    .chars()
+    .collect()
+});
+
+pub(crate) static CONFUSABLE_LANGUAGE_CLUSTERS: Lazy<Vec<HashSet<Language>>> = Lazy::new(|| {
+    vec![
+        hashset!(Bosnian, Croatian, Serbian),
+        hashset!(Bokmal, Danish, Nynorsk),
+        hashset!(Indonesian, Malay),
+        hashset!(Xhosa, Zulu),
+        hashset!(Sotho, Tswana),
+    ]
+});
+
+/// Maps the characters of an alphabet to the languages which are the only ones to use them.
+/// Each group of characters is shared by at least two languages of the same alphabet.
+pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<
+    HashMap<Alphabet, HashMap<&'static str, HashSet<Language>>>,
+> = Lazy::new(|| {
+    hashmap!(
+        Alphabet::Arabic => hashmap!(
+            "پچژگکی" => hashset!(Persian, Urdu)
+        ),
+        Alphabet::Cyrillic => hashmap!(
+            "ЉљЊњ" => hashset!(Macedonian, Serbian),
+            "ӘәҒғ" => hashset!(Azerbaijani, Kazakh),
+            "Јј" => hashset!(Azerbaijani, Macedonian, Serbian),
+            "ӨөҮү" => hashset!(Azerbaijani, Kazakh, Mongolian),
+            "Іі" => hashset!(Belarusian, Kazakh, Ukrainian),
+            "ЁёЭэ" => hashset!(Belarusian, Kazakh, Mongolian, Russian),
+            "ЩщЪъ" => hashset!(Bulgarian, Kazakh, Mongolian, Russian),
+            "Ыы" => hashset!(Azerbaijani, Belarusian, Kazakh, Mongolian, Russian)
+        ),
+        Alphabet::Latin => hashmap!(
             "Ãã" => hashset!(Portuguese, Vietnamese),
             "ĄąĘę" => hashset!(Lithuanian, Polish),
             "Żż" => hashset!(Polish, Romanian),
@@ -39,7 +100,6 @@ pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet
             "ŇňŤť" => hashset!(Czech, Slovak),
             "Ăă" => hashset!(Romanian, Vietnamese),
             "İıĞğ" => hashset!(Azerbaijani, Turkish),
-            "ЈјЉљЊњ" => hashset!(Macedonian, Serbian),
             "ĀāĒēĪī" => hashset!(Latvian, Maori, Yoruba),
             "ẸẹỌọ" => hashset!(Vietnamese, Yoruba),
 
@@ -48,9 +108,6 @@ pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet
             "Ďď" => hashset!(Czech, Romanian, Slovak),
             "ÐðÞþ" => hashset!(Icelandic, Latvian, Turkish),
             "Ûû" => hashset!(French, Hungarian, Latvian),
-            "Ćć" => hashset!(Bosnian, Croatian, Polish),
-            "Đđ" => hashset!(Bosnian, Croatian, Vietnamese),
-            "Іі" => hashset!(Belarusian, Kazakh, Ukrainian),
             "Ìì" => hashset!(Italian, Vietnamese, Yoruba),
 
             "Ëë" => hashset!(Afrikaans, Albanian, Dutch, French),
@@ -59,8 +116,6 @@ pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet
             "Õõ" => hashset!(Estonian, Hungarian, Portuguese, Vietnamese),
             "Ôô" => hashset!(French, Portuguese, Slovak, Vietnamese),
             "Øø" => hashset!(Bokmal, Danish, Nynorsk),
-            "ЁёЫыЭэ" => hashset!(Belarusian, Kazakh, Mongolian, Russian),
-            "ЩщЪъ" => hashset!(Bulgarian, Kazakh, Mongolian, Russian),
 
             "Òò" => hashset!(Catalan, Italian, Latvian, Vietnamese, Yoruba),
             "Ōō" => hashset!(Maori, Yoruba),
@@ -70,10 +125,14 @@ pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet
             "Àà" => hashset!(Catalan, French, Italian, Portuguese, Vietnamese),
             "Ææ" => hashset!(Bokmal, Danish, Icelandic, Nynorsk),
             "Åå" => hashset!(Bokmal, Danish, Nynorsk, Swedish),
+            "Ćć" => hashset!(Bosnian, Croatian, Polish, Serbian),
+            "Đđ" => hashset!(Bosnian, Croatian, Serbian, Vietnamese),
 
             "Üü" => hashset!(Azerbaijani, Catalan, Estonian, German, Hungarian, Spanish, Turkish),
 
-            "ČčŠšŽž" => hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene),
+            "ČčŠšŽž" => hashset!(
+                Bosnian, Czech, Croatian, Latvian, Lithuanian, Serbian, Slovak, Slovene
+            ),
 
             "Çç" => hashset!(
                 Albanian, Azerbaijani, Basque, Catalan, French, Latvian, Portuguese, Turkish
@@ -96,4 +155,5 @@ pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet
                 Spanish, Vietnamese, Yoruba
             )
         )
-    });
+    )
+});
@@ -14,25 +14,45 @@
  * limitations under the License.
  */
 
-use crate::alphabet::Alphabet;
+use crate::alphabet::{Alphabet, ScriptVariant};
+use crate::cache::{CacheKey, CacheStatistics, ResultCache};
 use crate::constant::{
-    CHARS_TO_LANGUAGES_MAPPING, JAPANESE_CHARACTER_SET, MULTIPLE_WHITESPACE, NO_LETTER, NUMBERS,
-    PUNCTUATION,
+    CHARS_TO_LANGUAGES_MAPPING, CONFUSABLE_LANGUAGE_CLUSTERS, JAPANESE_CHARACTER_SET,
+    MULTIPLE_WHITESPACE, NO_LETTER, NUMBERS, PUNCTUATION, SIMPLIFIED_CHINESE_CHARACTERS,
+    TRADITIONAL_CHINESE_CHARACTERS,
 };
+use crate::group::{LanguageGroup, LanguageOrGroup};
+use crate::homoglyph::normalize_homoglyphs;
+use crate::keyboard::{has_keyboard_layout, remap_keyboard_layout};
 use crate::language::Language;
 use crate::language::Language::*;
-use crate::model::TestDataLanguageModel;
+use crate::model::{fold_diacritics, TestDataLanguageModel};
 use crate::models::bigram_models::bigram_models;
 use crate::models::fivegram_models::fivegram_models;
+use crate::models::pairwise_models::{pairwise_models, LazyLanguagePairToModelMapping};
 use crate::models::quadrigram_models::quadrigram_models;
 use crate::models::trigram_models::trigram_models;
 use crate::models::unigram_models::unigram_models;
+use crate::models::word_models::{word_models, LazyLanguageToWordsMapping};
 use crate::models::LazyLanguageToNgramsMapping;
 use crate::ngram::Ngram;
+use crate::normalization::normalize_text;
+use crate::preprocessor::TextPreprocessor;
+use crate::proper_noun::find_proper_nouns;
+use crate::result::{DetectionResult, DocumentDetectionResult, Verification};
+use crate::sampling::SamplingStrategy;
+use crate::segmentation::split_into_words;
+use crate::transliteration::{
+    deromanize, is_outranked_by_native_script_rivals, is_romanizable, transliterate,
+};
 use cfg_if::cfg_if;
 use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
 
 cfg_if! {
@@ -44,10 +64,116 @@ cfg_if! {
     }
 }
 
+/// The minimum amount by which the average logarithmized trigram probability of text remapped
+/// to another keyboard layout must exceed the one of the original text for it to be corrected.
+const KEYBOARD_LAYOUT_CORRECTION_THRESHOLD: f64 = 2.0;
+
+/// The maximum amount by which the average logarithmized trigram probability of deromanized text
+/// may fall short of the one of the original text in any language written in Latin script
+/// for the romanized language to be considered at all.
+const ROMANIZATION_THRESHOLD: f64 = 0.5;
+
+/// The probability assumed for trigrams of which not even the unigram is known to a language.
+const UNKNOWN_NGRAM_PROBABILITY: f64 = 1e-6;
+
+/// The factor by which the logarithmized probabilities of ngrams which only occur
+/// within proper nouns are multiplied, so that they contribute less to the detection.
+const PROPER_NOUN_WEIGHT: f64 = 0.25;
+
+/// The number of ngrams after which candidate languages are pruned in adaptive mode.
+const PRUNING_CHUNK_SIZE: usize = 64;
+
+/// The logarithmized likelihood, relative to the language decided on by the rule-based engine,
+/// which is assigned to all other languages when evidence from several texts is combined.
+/// It corresponds to the decided language being about 150 times as likely as any other one.
+const RULE_VOTE_LOG_LIKELIHOOD: f64 = -5.0;
+
+/// The minimum margin by which the confidence value of a confusable language may fall short of
+/// the most likely one for the language to still be reconsidered by the pairwise models.
+const CONFUSABLE_LANGUAGES_MARGIN: f64 = 0.05;
+
+/// The slope of the logistic function which maps the margin of a language over its strongest
+/// competitor to the probability that a text is written in this language. It has been fitted
+/// by Platt scaling on held-out test data with `examples/verification_calibration.rs`.
+const VERIFICATION_SCORE_SLOPE: f64 = 0.2247;
+
+/// The evidence which the detection stages find for a given text.
+pub(crate) enum Evidence {
+    /// The text does not contain any letters.
+    None,
+    /// The rule-based engine has decided on a language on its own.
+    Rules(Language),
+    /// The statistical stage has computed a logarithmized likelihood for each
+    /// remaining language.
+    Statistics {
+        words: Vec<String>,
+        log_likelihoods: HashMap<Language, f64>,
+    },
+}
+
+impl Evidence {
+    /// Returns the logarithmized likelihood of each of the given languages relative to the
+    /// most likely one, so that the evidence of several texts can be added up. Languages
+    /// without a likelihood of their own are considered as unlikely as the least likely one.
+    /// If there is no evidence, `None` is returned.
+    pub(crate) fn to_relative_log_likelihoods(
+        &self,
+        languages: &HashSet<Language>,
+    ) -> Option<HashMap<Language, f64>> {
+        match self {
+            Evidence::None => None,
+            Evidence::Rules(language) => Some(
+                languages
+                    .iter()
+                    .map(|it| {
+                        let log_likelihood = if it == language {
+                            0.0
+                        } else {
+                            RULE_VOTE_LOG_LIKELIHOOD
+                        };
+                        (it.clone(), log_likelihood)
+                    })
+                    .collect(),
+            ),
+            Evidence::Statistics {
+                log_likelihoods, ..
+            } => {
+                if log_likelihoods.is_empty() {
+                    return None;
+                }
+                let highest = log_likelihoods.values().cloned().fold(f64::MIN, f64::max);
+                let lowest = log_likelihoods.values().cloned().fold(f64::MAX, f64::min);
+
+                Some(
+                    languages
+                        .iter()
+                        .map(|language| {
+                            let log_likelihood = log_likelihoods.get(language).unwrap_or(&lowest);
+                            (language.clone(), log_likelihood - highest)
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
 /// This struct detects the language of given input text.
 pub struct LanguageDetector {
-    languages: HashSet<Language>,
+    pub(crate) languages: HashSet<Language>,
     minimum_relative_distance: f64,
+    pub(crate) use_folded_language_models: bool,
+    include_romanized_languages: bool,
+    correct_keyboard_layouts: bool,
+    normalize_homoglyphs: bool,
+    pub(crate) text_preprocessors: Vec<Arc<dyn TextPreprocessor>>,
+    pub(crate) down_weight_proper_nouns: bool,
+    pub(crate) weight_ngrams_by_count: bool,
+    sampling_strategy: Option<SamplingStrategy>,
+    check_sample_consistency: bool,
+    pruning_margin: Option<f64>,
+    cache: Option<Arc<ResultCache>>,
+    cache_fingerprint: Option<u64>,
     languages_with_unique_characters: HashSet<Language>,
     one_language_alphabets: HashMap<Alphabet, Language>,
     unigram_language_models: LazyLanguageToNgramsMapping,
@@ -55,10 +181,27 @@ pub struct LanguageDetector {
     trigram_language_models: LazyLanguageToNgramsMapping,
     quadrigram_language_models: LazyLanguageToNgramsMapping,
     fivegram_language_models: LazyLanguageToNgramsMapping,
+    word_language_models: LazyLanguageToWordsMapping,
+    pairwise_language_models: LazyLanguagePairToModelMapping,
 }
 
 impl LanguageDetector {
-    pub(crate) fn from(languages: HashSet<Language>, minimum_relative_distance: f64) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn from(
+        languages: HashSet<Language>,
+        minimum_relative_distance: f64,
+        use_folded_language_models: bool,
+        include_romanized_languages: bool,
+        correct_keyboard_layouts: bool,
+        normalize_homoglyphs: bool,
+        text_preprocessors: Vec<Arc<dyn TextPreprocessor>>,
+        down_weight_proper_nouns: bool,
+        weight_ngrams_by_count: bool,
+        sampling_strategy: Option<SamplingStrategy>,
+        check_sample_consistency: bool,
+        pruning_margin: Option<f64>,
+        cache: Option<Arc<ResultCache>>,
+    ) -> Self {
         let languages_with_unique_characters = languages
             .iter()
             .filter(|it| it.unique_characters().is_some())
@@ -68,9 +211,21 @@ impl LanguageDetector {
             .into_iter()
             .filter(|(_, language)| languages.contains(language))
             .collect();
-        Self {
+        let mut detector = Self {
             languages,
             minimum_relative_distance,
+            use_folded_language_models,
+            include_romanized_languages,
+            correct_keyboard_layouts,
+            normalize_homoglyphs,
+            text_preprocessors,
+            down_weight_proper_nouns,
+            weight_ngrams_by_count,
+            sampling_strategy,
+            check_sample_consistency,
+            pruning_margin,
+            cache,
+            cache_fingerprint: None,
             languages_with_unique_characters,
             one_language_alphabets,
             unigram_language_models: unigram_models(),
@@ -78,14 +233,248 @@ impl LanguageDetector {
             trigram_language_models: trigram_models(),
             quadrigram_language_models: quadrigram_models(),
             fivegram_language_models: fivegram_models(),
-        }
+            word_language_models: word_models(),
+            pairwise_language_models: pairwise_models(),
+        };
+        detector.cache_fingerprint = detector.compute_cache_fingerprint();
+        detector
     }
 
     /// Detects the language of given input text.
     /// If the language cannot be reliably detected, `None` is returned.
     pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
+        let text = self.sample_text(text.into());
+        let text = self.correct_keyboard_layout(&text, None).unwrap_or(text);
+        let confidence_values = self.compute_confidence_values_of_sample(text);
+        self.select_most_likely_language(&confidence_values)
+    }
+
+    /// Detects the language of given input text together with further details,
+    /// such as the script that the text is written in.
+    /// If the language cannot be reliably detected, `None` is returned.
+    ///
+    /// Serbian is only reported for text in Latin script if neither Bosnian nor Croatian
+    /// is among the languages to detect, as it cannot be told apart from them reliably.
+    pub fn detect_language_with_details_of<T: Into<String>>(
+        &self,
+        text: T,
+    ) -> Option<DetectionResult> {
+        self.detect_language_with_details_until(text.into(), None)
+    }
+
+    /// Detects the language of given input text within the given time budget.
+    ///
+    /// The rule-based engine runs first, followed by the ngram models in increasing order
+    /// from unigrams to fivegrams. If the budget is used up before all ngram orders have been
+    /// scored, the most likely language according to the orders scored so far is returned
+    /// and the [`DetectionResult`](./struct.DetectionResult.html) is marked as partial.
+    /// Keyboard layout correction, homoglyph normalization and the sample consistency check
+    /// are skipped as well once the budget is used up.
+    /// If the language cannot be reliably detected within the budget, `None` is returned.
+    pub fn detect_language_of_within<T: Into<String>>(
+        &self,
+        text: T,
+        budget: Duration,
+    ) -> Option<DetectionResult> {
+        self.detect_language_with_details_until(text.into(), Some(Instant::now() + budget))
+    }
+
+    fn detect_language_with_details_until(
+        &self,
+        original_text: String,
+        deadline: Option<Instant>,
+    ) -> Option<DetectionResult> {
+        let text = self.sample_text(original_text.clone());
+        let corrected_text = self.correct_keyboard_layout(&text, deadline);
+        let text = corrected_text.clone().unwrap_or(text);
+        let down_weighted_words = self.find_down_weighted_words(&text);
+        let cleaned_up_text = self.clean_up_input_text(text);
+        let (confidence_values, is_partial) = self.compute_confidence_values_until(
+            cleaned_up_text.clone(),
+            &down_weighted_words,
+            deadline,
+        );
+        let language = self.select_most_likely_language(&confidence_values)?;
+        let sample_agreement = self.compute_sample_agreement(&original_text, &language, deadline);
+        let normalized_text = if has_passed(deadline) {
+            None
+        } else {
+            self.normalize_mixed_script_words(&cleaned_up_text)
+        };
+        let has_normalized_homoglyphs = normalized_text.is_some();
+        let cleaned_up_text = normalized_text.unwrap_or(cleaned_up_text);
+        let words = self.split_text_into_words(&cleaned_up_text);
+        let alphabets = language.alphabets();
+        let detected_alphabet = self.detect_alphabet(&words);
+        let is_romanized = matches!(
+            detected_alphabet,
+            Some(alphabet) if self.is_written_romanized(&language, &alphabet)
+        );
+        let alphabet = match detected_alphabet {
+            Some(alphabet) if is_romanized || alphabets.contains(&alphabet) => Some(alphabet),
+            _ if alphabets.len() == 1 => alphabets.into_iter().next(),
+            _ => None,
+        };
+
+        let script_variant = match language {
+            Chinese => self.detect_chinese_script_variant(&cleaned_up_text),
+            _ => None,
+        };
+
+        Some(DetectionResult::new(
+            language,
+            alphabet,
+            script_variant,
+            is_romanized,
+            corrected_text,
+            has_normalized_homoglyphs,
+            down_weighted_words,
+            sample_agreement,
+            is_partial,
+        ))
+    }
+
+    /// Detects the language of given input text or, if that is not possible because
+    /// the most likely languages are too close to each other, their common language group.
+    ///
+    /// A group is returned only if all languages whose confidence values lie within the
+    /// minimum relative distance of the most likely language belong to it. Otherwise,
+    /// `None` is returned.
+    pub fn detect_language_or_group_of<T: Into<String>>(&self, text: T) -> Option<LanguageOrGroup> {
         let confidence_values = self.compute_language_confidence_values(text);
+        self.select_most_likely_language_or_group(&confidence_values)
+    }
+
+    /// Detects the language of a document which consists of several fields,
+    /// such as a title, a body and tags, each given together with its weight.
+    ///
+    /// For each field, the rule-based engine and the ngram models are applied separately.
+    /// Their findings are combined into one decision in which each field contributes in
+    /// proportion to its weight and to the number of its letters. Fields with a weight of 0.0
+    /// are detected but do not contribute to the decision. The returned
+    /// [`DocumentDetectionResult`](./struct.DocumentDetectionResult.html) contains the
+    /// per-field results as well, so that documents whose fields disagree can be found.
+    ///
+    /// ⚠ Panics if any weight is negative or not finite.
+    pub fn detect_language_of_document<T: AsRef<str>>(
+        &self,
+        fields: &[(T, f64)],
+    ) -> DocumentDetectionResult {
+        if fields
+            .iter()
+            .any(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+        {
+            panic!("field weights must be finite and not negative");
+        }
+
+        let mut log_likelihoods = HashMap::<Language, f64>::new();
+        let mut field_confidence_values = vec![];
+
+        for (text, weight) in fields {
+            let text = self.sample_text(text.as_ref().to_string());
+            let text = self.correct_keyboard_layout(&text, None).unwrap_or(text);
+            let letter_count = self.count_letters(&self.clean_up_input_text(text.clone()));
+            let evidence = self.collect_evidence_until(text, None).0;
+
+            if let Some(relative_log_likelihoods) =
+                evidence.to_relative_log_likelihoods(&self.languages)
+            {
+                for (language, log_likelihood) in relative_log_likelihoods {
+                    *log_likelihoods.entry(language).or_insert(0.0) +=
+                        weight * letter_count as f64 * log_likelihood;
+                }
+            }
+
+            field_confidence_values.push(self.rank_evidence(evidence));
+        }
+
+        let highest_log_likelihood = log_likelihoods.values().cloned().fold(f64::MIN, f64::max);
+        let confidence_values = self.sort_confidence_values(
+            log_likelihoods
+                .into_iter()
+                .map(|(language, log_likelihood)| {
+                    (language, (log_likelihood - highest_log_likelihood).exp())
+                })
+                .collect(),
+        );
+        let language = self.select_most_likely_language(&confidence_values);
+        let field_languages = field_confidence_values
+            .iter()
+            .map(|it| self.select_most_likely_language(it))
+            .collect();
+
+        DocumentDetectionResult::new(
+            language,
+            confidence_values,
+            field_languages,
+            field_confidence_values,
+        )
+    }
+
+    /// Verifies whether given input text is written in the given language.
+    ///
+    /// The likelihood of the given language is compared against the one of the strongest
+    /// competitor among the detector's other languages. The difference of both is scaled by the
+    /// square root of the number of letters in the text, as the evidence of overlapping ngrams
+    /// grows less than linearly with the text's length. This margin is then mapped to a score
+    /// between 0.0 and 1.0 by a logistic function whose slope has been fitted on held-out test
+    /// data, so that the score estimates the probability that the text is written in the given
+    /// language rather than in its strongest competitor. The score is 0.5 if both languages are
+    /// equally likely, and the language is verified if the score is greater than 0.5.
+    ///
+    /// If the script of the text alone rules out the given language, such as Cyrillic script
+    /// for German, the ngram models are not consulted at all and the score is 0.0. The score
+    /// is 0.0 as well if the given language is not one of the detector's languages or if the
+    /// text does not contain any letters.
+    pub fn verify_language<T: Into<String>>(&self, text: T, language: Language) -> Verification {
+        let text = self.sample_text(text.into());
+        let text = self.correct_keyboard_layout(&text, None).unwrap_or(text);
+        let cleaned_up_text = self.clean_up_input_text(text.clone());
+        let cleaned_up_text = self
+            .normalize_mixed_script_words(&cleaned_up_text)
+            .unwrap_or(cleaned_up_text);
+        let words = self.split_text_into_words(&cleaned_up_text);
+
+        if !self.languages.contains(&language) || words.is_empty() {
+            return Verification::new(language, 0.0, None, None, false);
+        }
+
+        if !self.filter_languages_by_rules(&words).contains(&language) {
+            let competitor = self.detect_language_with_rules(&words);
+            return Verification::new(language, 0.0, None, competitor, true);
+        }
+
+        let letter_count = self.count_letters(&cleaned_up_text);
+        let evidence = self.collect_evidence_until(text, None).0;
+        let log_likelihoods = match evidence.to_relative_log_likelihoods(&self.languages) {
+            Some(log_likelihoods) => log_likelihoods,
+            None => return Verification::new(language, 0.0, None, None, false),
+        };
+        let strongest_competitor = log_likelihoods
+            .iter()
+            .filter(|(it, _)| **it != language)
+            .max_by(|(first_language, first), (second_language, second)| {
+                first
+                    .partial_cmp(second)
+                    .unwrap()
+                    .then(second_language.cmp(first_language))
+            });
+
+        let (competitor, competitor_log_likelihood) = match strongest_competitor {
+            Some((competitor, log_likelihood)) => (competitor.clone(), *log_likelihood),
+            None => return Verification::new(language, 1.0, None, None, false),
+        };
+        let margin =
+            (log_likelihoods[&language] - competitor_log_likelihood) * (letter_count as f64).sqrt();
+        let score = 1.0 / (1.0 + (-VERIFICATION_SCORE_SLOPE * margin).exp());
+
+        Verification::new(language, score, Some(margin), Some(competitor), false)
+    }
 
+    pub(crate) fn select_most_likely_language(
+        &self,
+        confidence_values: &[(Language, f64)],
+    ) -> Option<Language> {
         if confidence_values.is_empty() {
             return None;
         }
@@ -114,6 +503,32 @@ impl LanguageDetector {
         Some(most_likely_language.clone())
     }
 
+    fn select_most_likely_language_or_group(
+        &self,
+        confidence_values: &[(Language, f64)],
+    ) -> Option<LanguageOrGroup> {
+        if let Some(language) = self.select_most_likely_language(confidence_values) {
+            return Some(LanguageOrGroup::Language(language));
+        }
+
+        let (most_likely_language, most_likely_language_probability) = confidence_values.first()?;
+        let group = LanguageGroup::from_language(most_likely_language)?;
+        let group_languages = group.languages();
+        let all_candidates_in_group = confidence_values
+            .iter()
+            .take_while(|(_, probability)| {
+                (most_likely_language_probability - probability) < self.minimum_relative_distance
+                    || (most_likely_language_probability - probability).abs() < f64::EPSILON
+            })
+            .all(|(language, _)| group_languages.contains(language));
+
+        if all_candidates_in_group {
+            Some(LanguageOrGroup::Group(group))
+        } else {
+            None
+        }
+    }
+
     /// Computes confidence values for each language considered possible for the given input text.
     ///
     /// A vector of all possible languages is returned, sorted by their confidence value in
@@ -133,39 +548,221 @@ impl LanguageDetector {
         &self,
         text: T,
     ) -> Vec<(Language, f64)> {
-        let mut values = vec![];
-        let cleaned_up_text = self.clean_up_input_text(text.into());
+        self.compute_confidence_values_of_sample(self.sample_text(text.into()))
+    }
+
+    fn compute_confidence_values_of_sample(&self, text: String) -> Vec<(Language, f64)> {
+        let down_weighted_words = self.find_down_weighted_words(&text);
+        let cleaned_up_text = self.clean_up_input_text(text);
+        self.compute_confidence_values_until(cleaned_up_text, &down_weighted_words, None)
+            .0
+    }
+
+    /// Computes the confidence values for the given cleaned up text, stopping at the first
+    /// ngram order which starts after the given deadline. Returns whether this has happened
+    /// as well.
+    fn compute_confidence_values_until(
+        &self,
+        cleaned_up_text: String,
+        down_weighted_words: &[String],
+        deadline: Option<Instant>,
+    ) -> (Vec<(Language, f64)>, bool) {
+        let cache = self.cache.as_ref().zip(self.cache_fingerprint);
+        let cache_key = cache.map(|(_, options_fingerprint)| CacheKey {
+            options_fingerprint,
+            cleaned_up_text: cleaned_up_text.clone(),
+            down_weighted_words: down_weighted_words.to_vec(),
+        });
+
+        if let (Some((cache, _)), Some(key)) = (cache, &cache_key) {
+            if let Some(confidence_values) = cache.get(key) {
+                return (confidence_values, false);
+            }
+        }
+
+        let (evidence, is_partial) = self.collect_evidence_of_cleaned_up_text(
+            cleaned_up_text,
+            down_weighted_words,
+            deadline,
+        );
+        let confidence_values = self.rank_evidence(evidence);
+
+        if let (Some((cache, _)), Some(key)) = (cache, cache_key) {
+            if !is_partial {
+                cache.insert(key, confidence_values.clone());
+            }
+        }
+
+        (confidence_values, is_partial)
+    }
+
+    /// Returns how often the result cache has been used
+    /// or `None` if the cache has not been enabled.
+    pub fn cache_statistics(&self) -> Option<CacheStatistics> {
+        self.cache.as_ref().map(|it| it.statistics())
+    }
+
+    /// Computes a fingerprint of all options which the cached confidence values depend on,
+    /// so that detectors built with different options can share the same cache. Returns
+    /// `None` if a text preprocessor has no stable id, in which case nothing is cached.
+    fn compute_cache_fingerprint(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        self.languages
+            .iter()
+            .sorted()
+            .collect_vec()
+            .hash(&mut hasher);
+        self.minimum_relative_distance.to_bits().hash(&mut hasher);
+        self.use_folded_language_models.hash(&mut hasher);
+        self.include_romanized_languages.hash(&mut hasher);
+        self.normalize_homoglyphs.hash(&mut hasher);
+        self.down_weight_proper_nouns.hash(&mut hasher);
+        self.weight_ngrams_by_count.hash(&mut hasher);
+        self.pruning_margin.map(f64::to_bits).hash(&mut hasher);
+
+        for preprocessor in self.text_preprocessors.iter() {
+            preprocessor.id()?.hash(&mut hasher);
+        }
+
+        Some(hasher.finish())
+    }
+
+    fn rank_evidence(&self, evidence: Evidence) -> Vec<(Language, f64)> {
+        match evidence {
+            Evidence::None => vec![],
+            Evidence::Rules(language) => vec![(language, 1.0)],
+            Evidence::Statistics {
+                words,
+                log_likelihoods,
+            } => self.rank_languages(
+                &words.iter().map(|it| it.as_str()).collect_vec(),
+                log_likelihoods,
+            ),
+        }
+    }
+
+    /// Collects the evidence which the rule-based and the statistical stage find for
+    /// the given text.
+    pub(crate) fn collect_evidence(&self, text: String) -> Evidence {
+        self.collect_evidence_until(self.sample_text(text), None).0
+    }
+
+    /// Collects the evidence for the given text, stopping at the first ngram order which
+    /// starts after the given deadline. Returns whether this has happened as well.
+    fn collect_evidence_until(&self, text: String, deadline: Option<Instant>) -> (Evidence, bool) {
+        let proper_nouns = self.find_down_weighted_words(&text);
+        let cleaned_up_text = self.clean_up_input_text(text);
+        self.collect_evidence_of_cleaned_up_text(cleaned_up_text, &proper_nouns, deadline)
+    }
+
+    /// Collects the evidence for the given text which has been cleaned up already,
+    /// down-weighting the given words which have been found in the original text.
+    fn collect_evidence_of_cleaned_up_text(
+        &self,
+        cleaned_up_text: String,
+        proper_nouns: &[String],
+        deadline: Option<Instant>,
+    ) -> (Evidence, bool) {
+        let mut is_partial = false;
+        let cleaned_up_text = if has_passed(deadline) {
+            is_partial = self.normalize_homoglyphs;
+            cleaned_up_text
+        } else {
+            self.normalize_mixed_script_words(&cleaned_up_text)
+                .unwrap_or(cleaned_up_text)
+        };
 
         if cleaned_up_text.is_empty() || NO_LETTER.is_match(&cleaned_up_text) {
-            return values;
+            return (Evidence::None, is_partial);
         }
 
         let words = self.split_text_into_words(&cleaned_up_text);
         let language_detected_by_rules = self.detect_language_with_rules(&words);
 
         if let Some(language) = language_detected_by_rules {
-            values.push((language, 1.0));
-            return values;
+            return (Evidence::Rules(language), is_partial);
         }
 
-        let mut filtered_languages = self.filter_languages_by_rules(words);
+        let mut filtered_languages = self.filter_languages_by_rules(&words);
+        let down_weighted_positions = self.find_down_weighted_positions(&words, proper_nouns);
 
         if filtered_languages.len() == 1 {
             let filtered_language = filtered_languages.into_iter().next().unwrap();
-            values.push((filtered_language, 1.0));
-            return values;
+            return (Evidence::Rules(filtered_language), is_partial);
         }
 
+        let input_texts = self.transliterate_input_text(
+            &cleaned_up_text,
+            self.detect_alphabet(&words),
+            &filtered_languages,
+        );
         let mut all_probabilities = Vec::<HashMap<Language, f64>>::new();
         let mut unigram_counts = HashMap::<Language, u32>::new();
 
         for i in 1..6 {
+            if has_passed(deadline) {
+                is_partial = true;
+                break;
+            }
             if cleaned_up_text.chars().count() < i {
                 continue;
             }
-            let test_data_model = TestDataLanguageModel::from(&cleaned_up_text, i);
-            let probabilities =
-                self.compute_language_probabilities(&test_data_model, &filtered_languages);
+            let test_data_models = input_texts
+                .iter()
+                .map(|(text, languages)| {
+                    let use_folded_models =
+                        self.use_folded_language_models && fold_diacritics(text) == *text;
+                    (
+                        TestDataLanguageModel::from(text, i),
+                        languages,
+                        use_folded_models,
+                        self.find_down_weighted_ngrams(text, &down_weighted_positions, i),
+                    )
+                })
+                .collect_vec();
+            let mut probabilities = HashMap::new();
+
+            for (test_data_model, languages, use_folded_models, down_weighted_ngrams) in
+                test_data_models.iter()
+            {
+                let chunks = match self.pruning_margin {
+                    Some(_) => test_data_model.split_into_chunks(PRUNING_CHUNK_SIZE),
+                    None => vec![],
+                };
+                let chunks = if chunks.is_empty() {
+                    vec![test_data_model]
+                } else {
+                    chunks.iter().collect_vec()
+                };
+
+                for chunk in chunks {
+                    let candidates = filtered_languages
+                        .intersection(languages)
+                        .cloned()
+                        .collect();
+
+                    for (language, probability) in self.compute_language_probabilities(
+                        chunk,
+                        &candidates,
+                        *use_folded_models,
+                        down_weighted_ngrams,
+                    ) {
+                        *probabilities.entry(language).or_insert(0.0) += probability;
+                    }
+
+                    if let Some(margin) = self.pruning_margin {
+                        self.prune_languages(
+                            &mut filtered_languages,
+                            &all_probabilities,
+                            &probabilities,
+                            &unigram_counts,
+                            margin,
+                        );
+                    }
+                }
+            }
+
             let languages = probabilities.keys().collect_vec();
 
             if !languages.is_empty() {
@@ -176,17 +773,80 @@ impl LanguageDetector {
             }
 
             if i == 1 {
-                self.count_unigrams(&mut unigram_counts, &test_data_model, &filtered_languages);
+                for (test_data_model, languages, use_folded_models, _) in test_data_models.iter() {
+                    let candidates = filtered_languages
+                        .intersection(languages)
+                        .cloned()
+                        .collect();
+                    self.count_unigrams(
+                        &mut unigram_counts,
+                        test_data_model,
+                        &candidates,
+                        *use_folded_models,
+                    );
+                }
             }
 
             all_probabilities.push(probabilities);
         }
 
-        let summed_up_probabilities =
-            self.sum_up_probabilities(all_probabilities, unigram_counts, filtered_languages);
+        let log_likelihoods = self.combine_probabilities(
+            &input_texts,
+            all_probabilities,
+            unigram_counts,
+            filtered_languages,
+        );
+        let evidence = Evidence::Statistics {
+            words: words.iter().map(|it| it.to_string()).collect(),
+            log_likelihoods,
+        };
+
+        (evidence, is_partial)
+    }
+
+    /// Combines the probabilities of the given ngram orders and word models into
+    /// one logarithmized likelihood per language.
+    pub(crate) fn combine_probabilities(
+        &self,
+        input_texts: &[(String, HashSet<Language>)],
+        all_probabilities: Vec<HashMap<Language, f64>>,
+        unigram_counts: HashMap<Language, u32>,
+        filtered_languages: HashSet<Language>,
+    ) -> HashMap<Language, f64> {
+        let mut summed_up_probabilities = self.sum_up_probabilities(
+            all_probabilities,
+            unigram_counts.clone(),
+            filtered_languages,
+        );
+
+        if summed_up_probabilities.is_empty() {
+            return summed_up_probabilities;
+        }
+
+        let word_probabilities = self.compute_word_probabilities(
+            input_texts,
+            &summed_up_probabilities.keys().cloned().collect(),
+            &unigram_counts,
+        );
+
+        for (language, probability) in word_probabilities {
+            if let Some(sum) = summed_up_probabilities.get_mut(&language) {
+                *sum += probability;
+            }
+        }
+
+        summed_up_probabilities
+    }
 
+    /// Turns the given logarithmized likelihoods into confidence values,
+    /// sorted in descending order.
+    pub(crate) fn rank_languages(
+        &self,
+        words: &[&str],
+        summed_up_probabilities: HashMap<Language, f64>,
+    ) -> Vec<(Language, f64)> {
         if summed_up_probabilities.is_empty() {
-            return values;
+            return vec![];
         }
 
         let highest_probability = summed_up_probabilities
@@ -196,9 +856,23 @@ impl LanguageDetector {
             .next()
             .unwrap();
 
-        summed_up_probabilities
+        let confidence_values = summed_up_probabilities
             .into_iter()
             .map(|(language, probability)| (language, highest_probability / probability))
+            .collect_vec();
+
+        self.disambiguate_confusable_languages(
+            words,
+            self.sort_confidence_values(confidence_values),
+        )
+    }
+
+    fn sort_confidence_values(
+        &self,
+        confidence_values: Vec<(Language, f64)>,
+    ) -> Vec<(Language, f64)> {
+        confidence_values
+            .into_iter()
             .sorted_by(
                 |(first_language, first_probability), (second_language, second_probability)| {
                     let sorted_by_probability =
@@ -211,107 +885,494 @@ impl LanguageDetector {
             .collect_vec()
     }
 
-    fn clean_up_input_text(&self, text: String) -> String {
-        let trimmed = text.trim().to_lowercase();
-        let without_punctuation = PUNCTUATION.replace_all(&trimmed, "");
-        let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
-        let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&without_numbers, " ");
-        normalized_whitespace.to_string()
-    }
+    /// Reconsiders the most likely languages if all of them belong to the same cluster of
+    /// confusable languages, such as Bosnian, Croatian and Serbian.
+    ///
+    /// Only languages whose confidence values are within the minimum relative distance,
+    /// but at least within `CONFUSABLE_LANGUAGES_MARGIN`, of the most likely language are
+    /// reconsidered, so that a clear winner of the ngram stage is never overruled.
+    /// Each pair of leading languages is decided by its pairwise model. If one language wins
+    /// against all others, it swaps its confidence value with the formerly most likely language.
+    /// If a pairwise model is missing or no language wins all of its pairings, the confidence
+    /// values are returned unchanged.
+    fn disambiguate_confusable_languages(
+        &self,
+        words: &[&str],
+        confidence_values: Vec<(Language, f64)>,
+    ) -> Vec<(Language, f64)> {
+        let (most_likely_language, highest_value) = match confidence_values.first() {
+            Some((language, value)) => (language, *value),
+            None => return confidence_values,
+        };
+        let margin = self
+            .minimum_relative_distance
+            .max(CONFUSABLE_LANGUAGES_MARGIN);
 
-    fn split_text_into_words<'a>(&self, text: &'a str) -> Vec<&'a str> {
-        if text.contains(' ') {
-            text.split(' ').collect_vec()
-        } else {
-            vec![text]
-        }
-    }
+        let cluster = match CONFUSABLE_LANGUAGE_CLUSTERS
+            .iter()
+            .find(|it| it.contains(most_likely_language))
+        {
+            Some(cluster) => cluster,
+            None => return confidence_values,
+        };
 
-    fn detect_language_with_rules(&self, words: &[&str]) -> Option<Language> {
-        let mut total_language_counts = HashMap::<Option<&Language>, u32>::new();
-        let half_word_count = (words.len() as f64) * 0.5;
+        let candidates = confidence_values
+            .iter()
+            .take_while(|(language, value)| {
+                cluster.contains(language) && highest_value - value <= margin
+            })
+            .map(|(language, _)| language)
+            .collect_vec();
 
-        for word in words {
-            let mut word_language_counts = HashMap::<&Language, u32>::new();
+        if candidates.len() < 2 {
+            return confidence_values;
+        }
 
-            for character in word.chars() {
-                let mut is_match = false;
-                let mut buffer = [0; 4];
-                let char_str = character.encode_utf8(&mut buffer);
+        let mut wins = HashMap::<&Language, u32>::new();
 
-                for (alphabet, language) in self.one_language_alphabets.iter() {
-                    if alphabet.matches(char_str) {
-                        self.increment_counter(&mut word_language_counts, language);
-                        is_match = true;
-                    }
-                }
+        for (first_language, second_language) in candidates.iter().tuple_combinations() {
+            let key = if first_language < second_language {
+                ((*first_language).clone(), (*second_language).clone())
+            } else {
+                ((*second_language).clone(), (*first_language).clone())
+            };
 
-                if !is_match {
-                    if Alphabet::Han.matches(char_str) {
-                        self.increment_counter(&mut word_language_counts, &Chinese);
-                    } else if JAPANESE_CHARACTER_SET.is_match(char_str) {
-                        self.increment_counter(&mut word_language_counts, &Japanese);
-                    } else if Alphabet::Latin.matches(char_str)
-                        || Alphabet::Cyrillic.matches(char_str)
-                        || Alphabet::Devanagari.matches(char_str)
-                    {
-                        self.languages_with_unique_characters
-                            .iter()
-                            .filter(|it| it.unique_characters().unwrap().contains(character))
-                            .for_each(|it| self.increment_counter(&mut word_language_counts, it));
+            match self.pairwise_language_models.get(&key) {
+                Some(model) => {
+                    if let Some(language) = model.decide(words) {
+                        self.increment_counter(&mut wins, language);
                     }
                 }
+                None => return confidence_values,
             }
+        }
 
-            if word_language_counts.is_empty() {
-                self.increment_counter(&mut total_language_counts, None);
-            } else if word_language_counts.len() == 1 {
-                let counted_languages = word_language_counts.keys().collect_vec();
-                let language = counted_languages.first().unwrap();
-                if self.languages.contains(language) {
-                    self.increment_counter(&mut total_language_counts, Some(language));
-                } else {
-                    self.increment_counter(&mut total_language_counts, None);
-                }
-            } else if word_language_counts.contains_key(&Chinese)
-                && word_language_counts.contains_key(&Japanese)
-            {
-                self.increment_counter(&mut total_language_counts, Some(&Japanese));
-            } else {
-                let sorted_word_language_counts = word_language_counts
+        let winner = wins
+            .into_iter()
+            .find(|(_, count)| *count as usize == candidates.len() - 1)
+            .map(|(language, _)| language.clone());
+
+        match winner {
+            Some(language) if &language != most_likely_language => {
+                let winner_value = confidence_values
+                    .iter()
+                    .find(|(it, _)| it == &language)
+                    .unwrap()
+                    .1;
+                let swapped_confidence_values = confidence_values
                     .into_iter()
-                    .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+                    .enumerate()
+                    .map(|(idx, (it, value))| {
+                        if idx == 0 {
+                            (it, winner_value)
+                        } else if it == language {
+                            (it, highest_value)
+                        } else {
+                            (it, value)
+                        }
+                    })
                     .collect_vec();
-                let (most_frequent_language, first_count) = sorted_word_language_counts[0];
-                let (_, second_count) = sorted_word_language_counts[1];
 
-                if first_count > second_count && self.languages.contains(most_frequent_language) {
-                    self.increment_counter(
-                        &mut total_language_counts,
-                        Some(most_frequent_language),
-                    );
-                } else {
-                    self.increment_counter(&mut total_language_counts, None);
-                }
+                self.sort_confidence_values(swapped_confidence_values)
             }
+            _ => confidence_values,
         }
+    }
 
-        let unknown_language_count = *total_language_counts.get(&None).or(Some(&0)).unwrap() as f64;
+    /// Groups the given languages by the version of the input text that their models
+    /// can be applied to. Languages whose models have been trained on a different script than
+    /// the one the input text is written in receive a transliterated version of the text.
+    fn transliterate_input_text(
+        &self,
+        text: &str,
+        alphabet: Option<Alphabet>,
+        languages: &HashSet<Language>,
+    ) -> Vec<(String, HashSet<Language>)> {
+        let mut input_texts = HashMap::<String, HashSet<Language>>::new();
+
+        for language in languages.iter() {
+            let input_text = alphabet
+                .and_then(|it| {
+                    if self.is_written_romanized(language, &it) {
+                        deromanize(text, language)
+                    } else {
+                        transliterate(text, language, &it)
+                    }
+                })
+                .unwrap_or_else(|| text.to_string());
 
-        if unknown_language_count < half_word_count {
-            total_language_counts.remove(&None);
+            input_texts
+                .entry(input_text)
+                .or_default()
+                .insert(language.clone());
         }
 
-        if total_language_counts.is_empty() {
+        input_texts.into_iter().collect_vec()
+    }
+
+    /// Shortens the given text to the sample taken by the configured sampling strategy.
+    fn sample_text(&self, text: String) -> String {
+        match self.sampling_strategy {
+            Some(strategy) => strategy.sample(&text).join(" "),
+            None => text,
+        }
+    }
+
+    /// Detects the language of each window of the given text's sample separately and returns
+    /// the share of windows whose language equals the given one. `None` is returned if the
+    /// consistency check is disabled, if the sample does not consist of several windows or
+    /// if the given deadline passes before all windows have been detected.
+    fn compute_sample_agreement(
+        &self,
+        text: &str,
+        language: &Language,
+        deadline: Option<Instant>,
+    ) -> Option<f64> {
+        if !self.check_sample_consistency {
             return None;
         }
 
-        if total_language_counts.len() == 1 {
-            return total_language_counts.iter().next().unwrap().0.cloned();
+        let windows = self.sampling_strategy?.sample(text);
+
+        if windows.len() < 2 {
+            return None;
         }
 
-        let sorted_total_language_counts = total_language_counts
-            .into_iter()
+        let mut agreeing_window_count = 0;
+
+        for window in windows.iter() {
+            if has_passed(deadline) {
+                return None;
+            }
+            let confidence_values = self.compute_confidence_values_of_sample(window.to_string());
+            if self
+                .select_most_likely_language(&confidence_values)
+                .as_ref()
+                == Some(language)
+            {
+                agreeing_window_count += 1;
+            }
+        }
+
+        Some(agreeing_window_count as f64 / windows.len() as f64)
+    }
+
+    /// Returns the words of the given text which are most likely proper nouns
+    /// and therefore contribute less to the detection. Capitalized words known
+    /// to the word models of the languages to detect only count if they are part
+    /// of a sequence of capitalized words.
+    pub(crate) fn find_down_weighted_words(&self, text: &str) -> Vec<String> {
+        if !self.down_weight_proper_nouns {
+            return vec![];
+        }
+
+        let preprocessed = self
+            .text_preprocessors
+            .iter()
+            .fold(text.to_string(), |text, preprocessor| {
+                preprocessor.preprocess(&text)
+            });
+
+        find_proper_nouns(&preprocessed, |word| self.is_known_word(word))
+    }
+
+    /// Returns `true` if the word model of any of the languages to detect contains
+    /// the given word once it has been normalized.
+    fn is_known_word(&self, word: &str) -> bool {
+        let normalized_word = normalize_text(word);
+        self.languages
+            .iter()
+            .filter_map(|language| self.word_language_models.get(language))
+            .any(|model| model.get_relative_frequency(&normalized_word) > 0.0)
+    }
+
+    /// Returns the positions of the given cleaned up words which match any of the given
+    /// proper nouns once these have been cleaned up in the same way.
+    pub(crate) fn find_down_weighted_positions(
+        &self,
+        words: &[&str],
+        proper_nouns: &[String],
+    ) -> HashSet<usize> {
+        if proper_nouns.is_empty() {
+            return hashset!();
+        }
+
+        let cleaned_up_proper_nouns = proper_nouns
+            .iter()
+            .flat_map(|word| {
+                let cleaned_up_word = self.clean_up_input_text(word.clone());
+                let normalized_word = self
+                    .normalize_mixed_script_words(&cleaned_up_word)
+                    .unwrap_or(cleaned_up_word);
+                self.split_text_into_words(&normalized_word)
+                    .into_iter()
+                    .map(|it| it.to_string())
+                    .collect_vec()
+            })
+            .collect::<HashSet<_>>();
+
+        words
+            .iter()
+            .positions(|word| cleaned_up_proper_nouns.contains(*word))
+            .collect()
+    }
+
+    /// Returns the ngrams of the given length which only occur within the words
+    /// of the given text that are located at the down-weighted positions.
+    pub(crate) fn find_down_weighted_ngrams(
+        &self,
+        text: &str,
+        down_weighted_positions: &HashSet<usize>,
+        ngram_length: usize,
+    ) -> HashSet<Ngram> {
+        if down_weighted_positions.is_empty() {
+            return hashset!();
+        }
+
+        let (down_weighted_words, other_words): (Vec<_>, Vec<_>) = self
+            .split_text_into_words(text)
+            .into_iter()
+            .enumerate()
+            .partition(|(position, _)| down_weighted_positions.contains(position));
+        let join_words =
+            |words: Vec<(usize, &str)>| words.into_iter().map(|(_, word)| word).join(" ");
+        let other_ngrams =
+            TestDataLanguageModel::from(&join_words(other_words), ngram_length).ngrams;
+
+        TestDataLanguageModel::from(&join_words(down_weighted_words), ngram_length)
+            .ngrams
+            .into_iter()
+            .filter(|ngram| !other_ngrams.contains(ngram))
+            .collect()
+    }
+
+    pub(crate) fn normalize_mixed_script_words(&self, text: &str) -> Option<String> {
+        if self.normalize_homoglyphs {
+            normalize_homoglyphs(text)
+        } else {
+            None
+        }
+    }
+
+    /// Remaps Latin input text through the keyboard layouts of the detector's languages and
+    /// returns the remapped text which is most plausible in the respective language, but only
+    /// if it is far more plausible than the original text in any language written in Latin script.
+    /// Nothing is remapped anymore once the given deadline has passed.
+    fn correct_keyboard_layout(&self, text: &str, deadline: Option<Instant>) -> Option<String> {
+        if !self.correct_keyboard_layouts || has_passed(deadline) {
+            return None;
+        }
+
+        let cleaned_up_text = self.clean_up_input_text(text.to_string());
+        let words = self.split_text_into_words(&cleaned_up_text);
+
+        if self.detect_alphabet(&words) != Some(Alphabet::Latin) {
+            return None;
+        }
+
+        let original_plausibility = self
+            .languages
+            .iter()
+            .filter(|it| it.alphabets().contains(&Alphabet::Latin))
+            .map(|it| self.compute_text_plausibility(&cleaned_up_text, it))
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        self.languages
+            .iter()
+            .filter(|it| has_keyboard_layout(it))
+            .filter_map(|language| {
+                if has_passed(deadline) {
+                    return None;
+                }
+                let remapped_text = remap_keyboard_layout(text, language)?;
+                let cleaned_up_remapped_text = self.clean_up_input_text(remapped_text.clone());
+                let plausibility =
+                    self.compute_text_plausibility(&cleaned_up_remapped_text, language);
+                Some((remapped_text, plausibility))
+            })
+            .filter(|(_, plausibility)| {
+                plausibility - original_plausibility > KEYBOARD_LAYOUT_CORRECTION_THRESHOLD
+            })
+            .max_by(|(_, first), (_, second)| first.partial_cmp(second).unwrap())
+            .map(|(remapped_text, _)| remapped_text)
+    }
+
+    /// Computes the average logarithmized probability of the trigrams of the given text
+    /// in the given language, backing off to lower-order ngrams for unknown trigrams.
+    fn compute_text_plausibility(&self, text: &str, language: &Language) -> f64 {
+        let trigram_model = TestDataLanguageModel::from(text, 3);
+
+        if trigram_model.ngrams.is_empty() {
+            return f64::NEG_INFINITY;
+        }
+
+        let sum = trigram_model
+            .ngrams
+            .iter()
+            .map(|ngram| {
+                ngram
+                    .range_of_lower_order_ngrams()
+                    .map(|it| self.look_up_ngram_probability(language, &it, false))
+                    .find(|&it| it > 0.0)
+                    .unwrap_or(UNKNOWN_NGRAM_PROBABILITY)
+                    .ln()
+            })
+            .sum::<f64>();
+
+        sum / trigram_model.ngrams.len() as f64
+    }
+
+    /// Returns `true` if the given language is not written in the given script
+    /// but its romanized form is to be considered as well.
+    fn is_written_romanized(&self, language: &Language, alphabet: &Alphabet) -> bool {
+        self.include_romanized_languages
+            && *alphabet == Alphabet::Latin
+            && !language.alphabets().contains(alphabet)
+            && is_romanizable(language)
+    }
+
+    /// Returns the languages which the given words might be a romanization of. A language
+    /// qualifies if the words, converted back into its native script, are about as plausible
+    /// in it as the original words are in any language written in Latin script. Texts without
+    /// any trigram to assess are no reason to rule out a language.
+    fn find_plausibly_romanized_languages(&self, words: &[&str]) -> HashSet<Language> {
+        let romanized_languages = self
+            .languages
+            .iter()
+            .filter(|it| self.is_written_romanized(it, &Alphabet::Latin))
+            .collect_vec();
+
+        if romanized_languages.is_empty() || self.detect_alphabet(words) != Some(Alphabet::Latin) {
+            return hashset!();
+        }
+
+        let text = words.join(" ");
+        let original_plausibility = self
+            .languages
+            .iter()
+            .filter(|it| it.alphabets().contains(&Alphabet::Latin))
+            .map(|it| self.compute_text_plausibility(&text, it))
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        romanized_languages
+            .into_iter()
+            .filter(|language| match deromanize(&text, language) {
+                Some(deromanized_text) => {
+                    let plausibility = self.compute_text_plausibility(&deromanized_text, language);
+                    plausibility.is_infinite()
+                        || original_plausibility.is_infinite()
+                        || plausibility - original_plausibility > -ROMANIZATION_THRESHOLD
+                }
+                None => false,
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn clean_up_input_text(&self, text: String) -> String {
+        let preprocessed = self
+            .text_preprocessors
+            .iter()
+            .fold(text, |text, preprocessor| preprocessor.preprocess(&text));
+        let normalized = normalize_text(preprocessed.trim());
+        let without_punctuation = PUNCTUATION.replace_all(&normalized, "");
+        let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
+        let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&without_numbers, " ");
+        normalized_whitespace.to_string()
+    }
+
+    pub(crate) fn split_text_into_words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        split_into_words(text)
+    }
+
+    pub(crate) fn detect_language_with_rules(&self, words: &[&str]) -> Option<Language> {
+        let mut total_language_counts = HashMap::<Option<&Language>, u32>::new();
+        let half_character_count = self.count_characters(words) as f64 * 0.5;
+
+        for word in words {
+            let mut word_language_counts = HashMap::<&Language, u32>::new();
+            let character_count = word.chars().count() as u32;
+
+            for character in word.chars() {
+                let mut is_match = false;
+                let mut buffer = [0; 4];
+                let char_str = character.encode_utf8(&mut buffer);
+
+                for (alphabet, language) in self.one_language_alphabets.iter() {
+                    if alphabet.matches(char_str) {
+                        self.increment_counter(&mut word_language_counts, language);
+                        is_match = true;
+                    }
+                }
+
+                if !is_match {
+                    if Alphabet::Han.matches(char_str) {
+                        self.increment_counter(&mut word_language_counts, &Chinese);
+                    } else if JAPANESE_CHARACTER_SET.is_match(char_str) {
+                        self.increment_counter(&mut word_language_counts, &Japanese);
+                    } else {
+                        self.languages_with_unique_characters
+                            .iter()
+                            .filter(|it| it.unique_characters().unwrap().contains(character))
+                            .for_each(|it| self.increment_counter(&mut word_language_counts, it));
+                    }
+                }
+            }
+
+            if word_language_counts.is_empty() {
+                self.increase_counter(&mut total_language_counts, None, character_count);
+            } else if word_language_counts.len() == 1 {
+                let counted_languages = word_language_counts.keys().collect_vec();
+                let language = counted_languages.first().unwrap();
+                if self.languages.contains(language) {
+                    self.increase_counter(
+                        &mut total_language_counts,
+                        Some(language),
+                        character_count,
+                    );
+                } else {
+                    self.increase_counter(&mut total_language_counts, None, character_count);
+                }
+            } else if word_language_counts.contains_key(&Chinese)
+                && word_language_counts.contains_key(&Japanese)
+            {
+                self.increase_counter(&mut total_language_counts, Some(&Japanese), character_count);
+            } else {
+                let sorted_word_language_counts = word_language_counts
+                    .into_iter()
+                    .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+                    .collect_vec();
+                let (most_frequent_language, first_count) = sorted_word_language_counts[0];
+                let (_, second_count) = sorted_word_language_counts[1];
+
+                if first_count > second_count && self.languages.contains(most_frequent_language) {
+                    self.increase_counter(
+                        &mut total_language_counts,
+                        Some(most_frequent_language),
+                        character_count,
+                    );
+                } else {
+                    self.increase_counter(&mut total_language_counts, None, character_count);
+                }
+            }
+        }
+
+        let unknown_language_count = *total_language_counts.get(&None).or(Some(&0)).unwrap() as f64;
+
+        if unknown_language_count < half_character_count {
+            total_language_counts.remove(&None);
+        }
+
+        if total_language_counts.is_empty() {
+            return None;
+        }
+
+        if total_language_counts.len() == 1 {
+            return total_language_counts.iter().next().unwrap().0.cloned();
+        }
+
+        let sorted_total_language_counts = total_language_counts
+            .into_iter()
             .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
             .collect_vec();
         let (most_frequent_language, first_count) = sorted_total_language_counts[0];
@@ -324,46 +1385,90 @@ impl LanguageDetector {
         most_frequent_language.cloned()
     }
 
-    fn filter_languages_by_rules(&self, words: Vec<&str>) -> HashSet<Language> {
+    /// Decides between Simplified and Traditional Chinese by counting the characters
+    /// which are used in only one of both variants.
+    fn detect_chinese_script_variant(&self, text: &str) -> Option<ScriptVariant> {
+        let mut simplified_count = 0;
+        let mut traditional_count = 0;
+
+        for character in text.chars() {
+            if SIMPLIFIED_CHINESE_CHARACTERS.contains(&character) {
+                simplified_count += 1;
+            } else if TRADITIONAL_CHINESE_CHARACTERS.contains(&character) {
+                traditional_count += 1;
+            }
+        }
+
+        match simplified_count.cmp(&traditional_count) {
+            Ordering::Greater => Some(ScriptVariant::Simplified),
+            Ordering::Less => Some(ScriptVariant::Traditional),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Returns the alphabet which most characters of the given words are written in.
+    /// Every word votes for its alphabet with the number of its characters.
+    fn detect_alphabet(&self, words: &[&str]) -> Option<Alphabet> {
         let mut detected_alphabets = HashMap::<Alphabet, u32>::new();
-        let half_word_count = (words.len() as f64) * 0.5;
 
         for word in words.iter() {
             for alphabet in Alphabet::iter() {
                 if alphabet.matches(word) {
-                    self.increment_counter(&mut detected_alphabets, alphabet);
+                    let character_count = word.chars().count() as u32;
+                    self.increase_counter(&mut detected_alphabets, alphabet, character_count);
                     break;
                 }
             }
         }
 
-        if detected_alphabets.is_empty() {
-            return self.languages.clone();
-        }
-
-        let most_frequent_alphabet = detected_alphabets
+        detected_alphabets
             .into_iter()
             .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
             .next()
-            .unwrap()
-            .0;
+            .map(|(alphabet, _)| alphabet)
+    }
+
+    pub(crate) fn filter_languages_by_rules(&self, words: &[&str]) -> HashSet<Language> {
+        let half_character_count = self.count_characters(words) as f64 * 0.5;
+        let most_frequent_alphabet = match self.detect_alphabet(words) {
+            Some(alphabet) => alphabet,
+            None => return self.languages.clone(),
+        };
 
+        let romanized_languages = self.find_plausibly_romanized_languages(words);
         let filtered_languages = self
             .languages
             .iter()
             .cloned()
-            .filter(|it| it.alphabets().contains(&most_frequent_alphabet))
+            .filter(|it| {
+                (it.alphabets().contains(&most_frequent_alphabet)
+                    && !is_outranked_by_native_script_rivals(
+                        it,
+                        &most_frequent_alphabet,
+                        &self.languages,
+                    ))
+                    || romanized_languages.contains(it)
+            })
             .collect::<HashSet<_>>();
 
+        let chars_to_languages_mapping =
+            match CHARS_TO_LANGUAGES_MAPPING.get(&most_frequent_alphabet) {
+                Some(mapping) => mapping,
+                None => return filtered_languages,
+            };
         let mut language_counts = HashMap::<&Language, u32>::new();
 
         for word in words.iter() {
-            for (characters, languages) in CHARS_TO_LANGUAGES_MAPPING.iter() {
+            for (characters, languages) in chars_to_languages_mapping.iter() {
                 let mut word_contains_char = false;
                 for character in characters.chars() {
                     if word.contains(character) {
                         for language in languages.iter() {
-                            self.increment_counter(&mut language_counts, language);
+                            self.increase_counter(
+                                &mut language_counts,
+                                language,
+                                word.chars().count() as u32,
+                            );
                         }
                         word_contains_char = true;
                         break;
@@ -377,7 +1482,7 @@ impl LanguageDetector {
 
         let languages_subset = language_counts
             .into_iter()
-            .filter(|(_, count)| (*count as f64) >= half_word_count)
+            .filter(|(_, count)| (*count as f64) >= half_character_count)
             .map(|(language, _)| language)
             .collect::<HashSet<_>>();
 
@@ -391,14 +1496,61 @@ impl LanguageDetector {
         }
     }
 
+    /// Removes the languages whose probabilities, summed up over the ngrams scored so far
+    /// and normalized in the same way as in `sum_up_probabilities`, fall behind the ones
+    /// of the most likely language by more than the given margin. Only languages which have
+    /// been scored in the current ngram order are compared. As the normalization requires
+    /// the unigram counts, nothing is pruned while scoring unigrams.
+    fn prune_languages(
+        &self,
+        filtered_languages: &mut HashSet<Language>,
+        previous_probabilities: &[HashMap<Language, f64>],
+        current_probabilities: &HashMap<Language, f64>,
+        unigram_counts: &HashMap<Language, u32>,
+        margin: f64,
+    ) {
+        let scores = current_probabilities
+            .iter()
+            .filter(|(language, _)| filtered_languages.contains(language))
+            .filter_map(|(language, probability)| {
+                let unigram_count = *unigram_counts.get(language)? as f64;
+                let previous_probability = previous_probabilities
+                    .iter()
+                    .filter_map(|it| it.get(language))
+                    .sum::<f64>();
+                Some((
+                    language.clone(),
+                    (previous_probability + probability) / unigram_count,
+                ))
+            })
+            .collect_vec();
+        let highest_score = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        for (language, score) in scores {
+            if highest_score / score < 1.0 - margin {
+                filtered_languages.remove(&language);
+            }
+        }
+    }
+
     fn compute_language_probabilities(
         &self,
         model: &TestDataLanguageModel,
         filtered_languages: &HashSet<Language>,
+        use_folded_models: bool,
+        down_weighted_ngrams: &HashSet<Ngram>,
     ) -> HashMap<Language, f64> {
         let mut probabilities = hashmap!();
         for language in filtered_languages.iter() {
-            let sum = self.compute_sum_of_ngram_probabilities(language, &model.ngrams);
+            let sum = self.compute_sum_of_ngram_probabilities(
+                language,
+                model,
+                use_folded_models,
+                down_weighted_ngrams,
+            );
             if sum < 0.0 {
                 probabilities.insert(language.clone(), sum);
             }
@@ -406,43 +1558,83 @@ impl LanguageDetector {
         probabilities
     }
 
-    fn compute_sum_of_ngram_probabilities(
+    pub(crate) fn compute_sum_of_ngram_probabilities(
         &self,
         language: &Language,
-        ngrams: &HashSet<Ngram>,
+        model: &TestDataLanguageModel,
+        use_folded_models: bool,
+        down_weighted_ngrams: &HashSet<Ngram>,
     ) -> f64 {
         let mut probabilities = vec![];
-        for ngram in ngrams.iter() {
+        for ngram in model.ngrams.iter() {
+            let mut weight = if down_weighted_ngrams.contains(ngram) {
+                PROPER_NOUN_WEIGHT
+            } else {
+                1.0
+            };
+            if self.weight_ngrams_by_count {
+                weight *= model.ngram_counts[ngram] as f64;
+            }
             for elem in ngram.range_of_lower_order_ngrams() {
-                let probability = self.look_up_ngram_probability(language, &elem);
+                let probability =
+                    self.look_up_ngram_probability(language, &elem, use_folded_models);
 
                 if probability > 0.0 {
-                    probabilities.push(probability);
+                    probabilities.push(probability.ln() * weight);
                     break;
                 }
             }
         }
-        probabilities.into_iter().map(|it| it.ln()).sum()
+        probabilities.into_iter().sum()
+    }
+
+    pub(crate) fn look_up_ngram_probability(
+        &self,
+        language: &Language,
+        ngram: &Ngram,
+        use_folded_models: bool,
+    ) -> f64 {
+        let ngram_length = ngram.value.chars().count();
+        let language_models = match ngram_length {
+            0 => panic!("zerogram detected"),
+            1..=5 => self.get_language_models(ngram_length),
+            _ => panic!("unsupported ngram length detected: {}", ngram_length),
+        };
+
+        match language_models.get(language) {
+            Some(model) if use_folded_models => model.get_folded_relative_frequency(
+                ngram,
+                self.look_up_joint_frequencies(language, ngram_length - 1),
+            ),
+            Some(model) => model.get_relative_frequency(ngram),
+            None => 0.0,
+        }
     }
 
-    fn look_up_ngram_probability(&self, language: &Language, ngram: &Ngram) -> f64 {
-        let language_models = match ngram.value.chars().count() {
+    fn get_language_models(&self, ngram_length: usize) -> LazyLanguageToNgramsMapping {
+        match ngram_length {
             5 => self.fivegram_language_models,
             4 => self.quadrigram_language_models,
             3 => self.trigram_language_models,
             2 => self.bigram_language_models,
-            1 => self.unigram_language_models,
-            0 => panic!("zerogram detected"),
-            _ => panic!(
-                "unsupported ngram length detected: {}",
-                ngram.value.chars().count()
-            ),
-        };
+            _ => self.unigram_language_models,
+        }
+    }
 
-        language_models
-            .get(language)
-            .unwrap()
-            .get_relative_frequency(ngram)
+    /// Returns the joint frequencies of the given language's model of the given ngram length,
+    /// which the folded models of the next higher order are computed from.
+    fn look_up_joint_frequencies(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+    ) -> Option<&'static HashMap<Ngram, f64>> {
+        if ngram_length == 0 {
+            return None;
+        }
+        let model = self.get_language_models(ngram_length).get(language)?;
+        let lower_order_joint_frequencies =
+            self.look_up_joint_frequencies(language, ngram_length - 1);
+        Some(model.get_joint_frequencies(lower_order_joint_frequencies))
     }
 
     fn count_unigrams(
@@ -450,10 +1642,11 @@ impl LanguageDetector {
         unigram_counts: &mut HashMap<Language, u32>,
         unigram_model: &TestDataLanguageModel,
         filtered_languages: &HashSet<Language>,
+        use_folded_models: bool,
     ) {
         for language in filtered_languages.iter() {
             for unigram in unigram_model.ngrams.iter() {
-                if self.look_up_ngram_probability(language, unigram) > 0.0 {
+                if self.look_up_ngram_probability(language, unigram, use_folded_models) > 0.0 {
                     self.increment_counter(unigram_counts, language.clone());
                 }
             }
@@ -488,16 +1681,117 @@ impl LanguageDetector {
         summed_up_probabilities
     }
 
+    /// Computes the summed up logarithmized word probability of the given words for each language.
+    ///
+    /// Like the ngram probabilities in [`sum_up_probabilities`](#method.sum_up_probabilities),
+    /// the sum is divided by the number of unigrams known to the respective language, so that
+    /// both are on the same per-unigram scale and each word counts as much as one ngram of
+    /// every order.
+    ///
+    /// Word models are optional. They are only taken into account if all given languages have
+    /// one as well as a unigram count and if at least one of the words is known to at least one
+    /// language. Otherwise, some languages would be unfairly favored or penalized in comparison
+    /// to the others because their sums could not be computed or scaled alike. Unknown words are assigned the frequency of the rarest word in the respective
+    /// model.
+    fn compute_word_probabilities(
+        &self,
+        input_texts: &[(String, HashSet<Language>)],
+        filtered_languages: &HashSet<Language>,
+        unigram_counts: &HashMap<Language, u32>,
+    ) -> HashMap<Language, f64> {
+        let word_models = input_texts
+            .iter()
+            .flat_map(|(text, languages)| {
+                let words = self.split_text_into_words(text);
+                languages
+                    .intersection(filtered_languages)
+                    .filter_map(|language| {
+                        self.word_language_models
+                            .get(language)
+                            .map(|model| (language, model, words.clone()))
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        if word_models.is_empty()
+            || word_models.len() < filtered_languages.len()
+            || word_models
+                .iter()
+                .any(|(language, _, _)| !unigram_counts.contains_key(language))
+        {
+            return hashmap!();
+        }
+
+        let is_any_word_known = word_models.iter().any(|(_, model, words)| {
+            words
+                .iter()
+                .any(|word| model.get_relative_frequency(word) > 0.0)
+        });
+
+        if !is_any_word_known {
+            return hashmap!();
+        }
+
+        word_models
+            .into_iter()
+            .map(|(language, model, words)| {
+                let unknown_word_probability = model.get_minimum_relative_frequency();
+                let sum = words
+                    .iter()
+                    .map(|word| {
+                        let probability = model.get_relative_frequency(word);
+                        if probability > 0.0 {
+                            probability.ln()
+                        } else {
+                            unknown_word_probability.ln()
+                        }
+                    })
+                    .sum::<f64>();
+
+                (language.clone(), sum / unigram_counts[language] as f64)
+            })
+            .collect()
+    }
+
     fn increment_counter<T: Eq + Hash>(&self, counts: &mut HashMap<T, u32>, key: T) {
+        self.increase_counter(counts, key, 1);
+    }
+
+    fn increase_counter<T: Eq + Hash>(&self, counts: &mut HashMap<T, u32>, key: T, amount: u32) {
         let counter = counts.entry(key).or_insert(0);
-        *counter += 1;
+        *counter += amount;
+    }
+
+    /// Counts the letters of the given cleaned up text, by which its evidence is scaled
+    /// when it is weighed against the evidence of other texts or languages.
+    fn count_letters(&self, cleaned_up_text: &str) -> usize {
+        cleaned_up_text
+            .chars()
+            .filter(|it| it.is_alphabetic())
+            .count()
     }
+
+    /// Counts the characters of the given words, by which the votes
+    /// of the rule-based engine are weighted.
+    fn count_characters(&self, words: &[&str]) -> usize {
+        words.iter().map(|word| word.chars().count()).sum()
+    }
+}
+
+/// Returns `true` if the given deadline exists and has passed already.
+fn has_passed(deadline: Option<Instant>) -> bool {
+    matches!(deadline, Some(deadline) if Instant::now() >= deadline)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::model::{PairwiseLanguageModel, WordLanguageModel};
+    use crate::models::pairwise_models::LanguagePairToModelMappingCell;
+    use crate::models::word_models::LanguageToWordsMappingCell;
     use crate::models::{LanguageToNgramsMappingCell, LazyTrainingDataLanguageModel};
+    use crate::preprocessor::{MentionFilter, UrlFilter};
     use float_cmp::approx_eq;
     use once_cell::sync::OnceCell;
     use rstest::*;
@@ -512,7 +1806,13 @@ mod tests {
             mock.expect_get_relative_frequency()
                 .withf(move |n| n == &Ngram::new(ngram))
                 .return_const(probability);
+            // the folded probabilities differ so that tests notice whether they are used
+            mock.expect_get_folded_relative_frequency()
+                .withf(move |n, _| n == &Ngram::new(ngram))
+                .return_const(probability / 2.0);
         }
+        mock.expect_get_joint_frequencies()
+            .return_const(HashMap::<Ngram, f64>::new());
         mock
     }
 
@@ -740,6 +2040,41 @@ mod tests {
         })
     }
 
+    // ##############################
+    // WORD MODELS
+    // ##############################
+
+    #[fixture]
+    fn word_language_models() -> LazyLanguageToWordsMapping {
+        static WORD_MODELS_FIXTURE: LanguageToWordsMappingCell = OnceCell::new();
+        WORD_MODELS_FIXTURE.get_or_init(|| {
+            hashmap!(
+                English => WordLanguageModel::from_json(
+                    r#"{"language":"ENGLISH","words":{"1/10":"the","1/20":"of"}}"#
+                ),
+                German => WordLanguageModel::from_json(
+                    r#"{"language":"GERMAN","words":{"1/10":"alter","1/100":"der"}}"#
+                )
+            )
+        })
+    }
+
+    // ##############################
+    // PAIRWISE MODELS
+    // ##############################
+
+    #[fixture]
+    fn pairwise_language_models() -> LazyLanguagePairToModelMapping {
+        static PAIRWISE_MODELS_FIXTURE: LanguagePairToModelMappingCell = OnceCell::new();
+        PAIRWISE_MODELS_FIXTURE.get_or_init(|| {
+            hashmap!(
+                (Bosnian, Croatian) => PairwiseLanguageModel::from_json(
+                    r#"{"languages":["BOSNIAN","CROATIAN"],"words":{"šta":1.5,"što":-1.5},"ngrams":{"tko":-0.5}}"#
+                )
+            )
+        })
+    }
+
     // ##############################
     // EMPTY NGRAM MODELS
     // ##############################
@@ -750,6 +2085,18 @@ mod tests {
         EMPTY_MODELS_FIXTURE.get_or_init(|| hashmap!())
     }
 
+    #[fixture]
+    fn empty_word_models() -> LazyLanguageToWordsMapping {
+        static EMPTY_WORD_MODELS_FIXTURE: LanguageToWordsMappingCell = OnceCell::new();
+        EMPTY_WORD_MODELS_FIXTURE.get_or_init(|| hashmap!())
+    }
+
+    #[fixture]
+    fn empty_pairwise_models() -> LazyLanguagePairToModelMapping {
+        static EMPTY_PAIRWISE_MODELS_FIXTURE: LanguagePairToModelMappingCell = OnceCell::new();
+        EMPTY_PAIRWISE_MODELS_FIXTURE.get_or_init(|| hashmap!())
+    }
+
     // ##############################
     // TEST DATA MODELS
     // ##############################
@@ -760,8 +2107,12 @@ mod tests {
             .iter()
             .map(|&it| Ngram::new(it))
             .collect::<HashSet<_>>();
+        let ngram_counts = ngrams.iter().map(|it| (it.clone(), 1)).collect();
 
-        TestDataLanguageModel { ngrams }
+        TestDataLanguageModel {
+            ngrams,
+            ngram_counts,
+        }
     }
 
     // ##############################
@@ -769,16 +2120,30 @@ mod tests {
     // ##############################
 
     #[fixture]
-    fn detector_for_english_and_german(
+    pub(crate) fn detector_for_english_and_german(
         unigram_language_models: LazyLanguageToNgramsMapping,
         bigram_language_models: LazyLanguageToNgramsMapping,
         trigram_language_models: LazyLanguageToNgramsMapping,
         quadrigram_language_models: LazyLanguageToNgramsMapping,
         fivegram_language_models: LazyLanguageToNgramsMapping,
+        empty_word_models: LazyLanguageToWordsMapping,
+        empty_pairwise_models: LazyLanguagePairToModelMapping,
     ) -> LanguageDetector {
         LanguageDetector {
             languages: hashset!(English, German),
             minimum_relative_distance: 0.0,
+            use_folded_language_models: false,
+            include_romanized_languages: false,
+            correct_keyboard_layouts: false,
+            normalize_homoglyphs: false,
+            text_preprocessors: vec![],
+            down_weight_proper_nouns: false,
+            weight_ngrams_by_count: false,
+            sampling_strategy: None,
+            check_sample_consistency: false,
+            pruning_margin: None,
+            cache: None,
+            cache_fingerprint: None,
             languages_with_unique_characters: hashset!(),
             one_language_alphabets: hashmap!(),
             unigram_language_models,
@@ -786,12 +2151,38 @@ mod tests {
             trigram_language_models,
             quadrigram_language_models,
             fivegram_language_models,
+            word_language_models: empty_word_models,
+            pairwise_language_models: empty_pairwise_models,
+        }
+    }
+
+    #[fixture]
+    fn detector_for_english_and_german_with_word_models(
+        detector_for_english_and_german: LanguageDetector,
+        word_language_models: LazyLanguageToWordsMapping,
+    ) -> LanguageDetector {
+        LanguageDetector {
+            word_language_models,
+            ..detector_for_english_and_german
+        }
+    }
+
+    #[fixture]
+    fn detector_for_english_and_german_with_pairwise_models(
+        detector_for_english_and_german: LanguageDetector,
+        pairwise_language_models: LazyLanguagePairToModelMapping,
+    ) -> LanguageDetector {
+        LanguageDetector {
+            pairwise_language_models,
+            ..detector_for_english_and_german
         }
     }
 
     #[fixture]
     fn detector_for_all_languages(
         empty_language_models: LazyLanguageToNgramsMapping,
+        empty_word_models: LazyLanguageToWordsMapping,
+        empty_pairwise_models: LazyLanguagePairToModelMapping,
     ) -> LanguageDetector {
         let languages = Language::all();
         let languages_with_unique_characters = languages
@@ -808,6 +2199,18 @@ mod tests {
         LanguageDetector {
             languages,
             minimum_relative_distance: 0.0,
+            use_folded_language_models: false,
+            include_romanized_languages: false,
+            correct_keyboard_layouts: false,
+            normalize_homoglyphs: false,
+            text_preprocessors: vec![],
+            down_weight_proper_nouns: false,
+            weight_ngrams_by_count: false,
+            sampling_strategy: None,
+            check_sample_consistency: false,
+            pruning_margin: None,
+            cache: None,
+            cache_fingerprint: None,
             languages_with_unique_characters,
             one_language_alphabets,
             unigram_language_models: empty_language_models,
@@ -815,6 +2218,8 @@ mod tests {
             trigram_language_models: empty_language_models,
             quadrigram_language_models: empty_language_models,
             fivegram_language_models: empty_language_models,
+            word_language_models: empty_word_models,
+            pairwise_language_models: empty_pairwise_models,
         }
     }
 
@@ -838,6 +2243,39 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn assert_text_is_normalized_for_detection_as_for_training(
+        detector_for_all_languages: LanguageDetector,
+    ) {
+        let model = WordLanguageModel::from_json(
+            &WordLanguageModel::from_text(&["KIRMIZI"], &Turkish, "\\p{L}", 1).to_json(),
+        );
+        let cleaned_up_text = detector_for_all_languages.clean_up_input_text("KIRMIZI".to_string());
+
+        assert_eq!(cleaned_up_text, "kirmizi");
+        assert_eq!(model.get_relative_frequency(&cleaned_up_text), 1.0);
+    }
+
+    #[rstest]
+    fn assert_text_is_normalized_during_clean_up(detector_for_all_languages: LanguageDetector) {
+        assert_eq!(
+            detector_for_all_languages.clean_up_input_text("Ｃafe\u{301}\u{200B} ΟΔΟΣ".to_string()),
+            "café οδος"
+        );
+    }
+
+    #[rstest]
+    fn assert_text_is_preprocessed_during_clean_up(detector_for_all_languages: LanguageDetector) {
+        let detector = LanguageDetector {
+            text_preprocessors: vec![Arc::new(UrlFilter), Arc::new(MentionFilter)],
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.clean_up_input_text("@jane see https://example.com please".to_string()),
+            "see please"
+        );
+    }
+
     #[rstest]
     fn assert_text_is_split_into_words_correctly(detector_for_all_languages: LanguageDetector) {
         assert_eq!(
@@ -871,8 +2309,11 @@ mod tests {
         ngram: &str,
         expected_probability: f64,
     ) {
-        let probability = detector_for_english_and_german
-            .look_up_ngram_probability(&language, &Ngram::new(ngram));
+        let probability = detector_for_english_and_german.look_up_ngram_probability(
+            &language,
+            &Ngram::new(ngram),
+            false,
+        );
         assert_eq!(
             probability, expected_probability,
             "expected probability {} for language '{:?}' and ngram '{}', got {}",
@@ -880,12 +2321,51 @@ mod tests {
         );
     }
 
+    #[rstest(
+        use_folded_models,
+        expected_probability,
+        case(false, 0.01),
+        case(true, 0.03)
+    )]
+    fn assert_folded_ngram_probability_lookup_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        use_folded_models: bool,
+        expected_probability: f64,
+    ) {
+        static FRENCH_UNIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static FRENCH_UNIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+
+        let unigram_language_models = FRENCH_UNIGRAM_MODELS_FIXTURE.get_or_init(|| {
+            let model = FRENCH_UNIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                let mut mock = TrainingDataLanguageModel::new();
+                mock.expect_get_relative_frequency()
+                    .withf(|n| n == &Ngram::new("e"))
+                    .return_const(0.01);
+                mock.expect_get_folded_relative_frequency()
+                    .withf(|n, lower_order_joint_frequencies| {
+                        n == &Ngram::new("e") && lower_order_joint_frequencies.is_none()
+                    })
+                    .return_const(0.03);
+                mock
+            });
+            hashmap!(French => model)
+        });
+        let detector = LanguageDetector {
+            unigram_language_models,
+            ..detector_for_english_and_german
+        };
+        let probability =
+            detector.look_up_ngram_probability(&French, &Ngram::new("e"), use_folded_models);
+
+        assert_eq!(probability, expected_probability);
+    }
+
     #[rstest]
     #[should_panic(expected = "zerogram detected")]
     fn assert_ngram_probability_lookup_does_not_work_for_zerogram(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        detector_for_english_and_german.look_up_ngram_probability(&English, &Ngram::new(""));
+        detector_for_english_and_german.look_up_ngram_probability(&English, &Ngram::new(""), false);
     }
 
     #[rstest(
@@ -900,135 +2380,890 @@ mod tests {
             0.19_f64.ln() + 0.2_f64.ln() + 0.13_f64.ln()
         ),
         case(
-            // back off unknown Fivegram("aquas") to known Unigram("a")
-            hashset!("aquas"),
-            0.01_f64.ln()
+            // back off unknown Fivegram("aquas") to known Unigram("a")
+            hashset!("aquas"),
+            0.01_f64.ln()
+        )
+    )]
+    fn assert_summation_of_ngram_probabilities_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        ngrams: HashSet<&'static str>,
+        expected_sum_of_probabilities: f64,
+    ) {
+        let sum_of_probabilities = detector_for_english_and_german
+            .compute_sum_of_ngram_probabilities(
+                &English,
+                &test_data_model(ngrams.clone()),
+                false,
+                &hashset!(),
+            );
+
+        assert!(
+            approx_eq!(
+                f64,
+                sum_of_probabilities,
+                expected_sum_of_probabilities,
+                ulps = 1
+            ),
+            "expected sum {} for language '{:?}' and ngrams {:?}, got {}",
+            expected_sum_of_probabilities,
+            English,
+            ngrams,
+            sum_of_probabilities
+        );
+    }
+
+    #[rstest]
+    fn assert_down_weighted_ngrams_contribute_less(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let sum_of_probabilities = detector_for_english_and_german
+            .compute_sum_of_ngram_probabilities(
+                &English,
+                &test_data_model(hashset!("a", "l")),
+                false,
+                &hashset!(Ngram::new("l")),
+            );
+
+        assert!(approx_eq!(
+            f64,
+            sum_of_probabilities,
+            0.01_f64.ln() + 0.02_f64.ln() * PROPER_NOUN_WEIGHT,
+            ulps = 1
+        ));
+    }
+
+    #[rstest(
+        weight_ngrams_by_count,
+        expected_sum_of_probabilities,
+        case(false, 0.01_f64.ln() + 0.02_f64.ln()),
+        case(true, 3.0 * 0.01_f64.ln() + 2.0 * 0.02_f64.ln())
+    )]
+    fn assert_ngrams_are_weighted_by_count_if_desired(
+        detector_for_english_and_german: LanguageDetector,
+        weight_ngrams_by_count: bool,
+        expected_sum_of_probabilities: f64,
+    ) {
+        let detector = LanguageDetector {
+            weight_ngrams_by_count,
+            ..detector_for_english_and_german
+        };
+        let sum_of_probabilities = detector.compute_sum_of_ngram_probabilities(
+            &English,
+            &TestDataLanguageModel::from("ala la", 1),
+            false,
+            &hashset!(),
+        );
+
+        assert!(approx_eq!(
+            f64,
+            sum_of_probabilities,
+            expected_sum_of_probabilities,
+            ulps = 1
+        ));
+    }
+
+    #[rstest(
+        margin,
+        unigram_counts,
+        expected_languages,
+        case(0.5, hashmap!(English => 3, German => 3), hashset!(English)),
+        case(0.6, hashmap!(English => 3, German => 3), hashset!(English, German)),
+        case(0.5, hashmap!(English => 3, German => 6), hashset!(English, German)),
+        case(0.5, hashmap!(), hashset!(English, German))
+    )]
+    fn assert_languages_falling_behind_are_pruned(
+        detector_for_english_and_german: LanguageDetector,
+        margin: f64,
+        unigram_counts: HashMap<Language, u32>,
+        expected_languages: HashSet<Language>,
+    ) {
+        let mut filtered_languages = hashset!(English, German);
+
+        detector_for_english_and_german.prune_languages(
+            &mut filtered_languages,
+            &[hashmap!(English => -10.0, German => -12.0)],
+            &hashmap!(English => -5.0, German => -20.0),
+            &unigram_counts,
+            margin,
+        );
+
+        assert_eq!(filtered_languages, expected_languages);
+    }
+
+    #[rstest]
+    fn assert_pruning_keeps_decision_for_german_noun_alter(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let detector = LanguageDetector {
+            pruning_margin: Some(0.5),
+            ..detector_for_english_and_german
+        };
+        assert_eq!(detector.detect_language_of("Alter"), Some(German));
+    }
+
+    #[rstest(
+        test_data_model,
+        expected_probabilities,
+        case::unigram_model(
+            test_data_model(hashset!("a", "l", "t", "e", "r")),
+            hashmap!(
+                English => 0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln(),
+                German => 0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln()
+            )
+        ),
+        case::trigram_model(
+            test_data_model(hashset!("alt", "lte", "ter", "wxy")),
+            hashmap!(
+                English => 0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln(),
+                German => 0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln()
+            )
+        ),
+        case::quadrigram_model(
+            test_data_model(hashset!("alte", "lter", "wxyz")),
+            hashmap!(
+                English => 0.25_f64.ln() + 0.26_f64.ln(),
+                German => 0.27_f64.ln() + 0.28_f64.ln()
+            )
+        )
+    )]
+    fn assert_computation_of_language_probabilities_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        test_data_model: TestDataLanguageModel,
+        expected_probabilities: HashMap<Language, f64>,
+    ) {
+        let probabilities = detector_for_english_and_german.compute_language_probabilities(
+            &test_data_model,
+            &hashset!(English, German),
+            false,
+            &hashset!(),
+        );
+
+        for (language, probability) in probabilities {
+            let expected_probability = expected_probabilities[&language];
+
+            assert!(
+                approx_eq!(f64, probability, expected_probability, ulps = 1),
+                "expected probability {} for language '{:?}', got {}",
+                expected_probability,
+                language,
+                probability
+            );
+        }
+    }
+
+    #[rstest]
+    fn assert_computation_of_confidence_values_works_correctly(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let unigram_count_for_both_languages = 5.0;
+
+        let total_probability_for_german = (
+            // unigrams
+            0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln() +
+            // bigrams
+            0.15_f64.ln() + 0.16_f64.ln() + 0.17_f64.ln() + 0.18_f64.ln() +
+            // trigrams
+            0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln() +
+            // quadrigrams
+            0.27_f64.ln() + 0.28_f64.ln() +
+            // fivegrams
+            0.3_f64.ln()
+        ) / unigram_count_for_both_languages;
+
+        let total_probability_for_english = (
+            // unigrams
+            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln() +
+            // bigrams
+            0.11_f64.ln() + 0.12_f64.ln() + 0.13_f64.ln() + 0.14_f64.ln() +
+            // trigrams
+            0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln() +
+            // quadrigrams
+            0.25_f64.ln() + 0.26_f64.ln() +
+            // fivegrams
+            0.29_f64.ln()
+        ) / unigram_count_for_both_languages;
+
+        let expected_confidence_for_german = 1.0;
+        let expected_confidence_for_english =
+            total_probability_for_german / total_probability_for_english;
+
+        let confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values("Alter");
+
+        assert_eq!(
+            confidence_values[0],
+            (German, expected_confidence_for_german)
+        );
+        assert_eq!(confidence_values[1].0, English);
+        assert!(approx_eq!(
+            f64,
+            confidence_values[1].1,
+            expected_confidence_for_english,
+            ulps = 1
+        ));
+    }
+
+    #[rstest]
+    fn assert_computation_of_word_probabilities_works_correctly(
+        detector_for_english_and_german_with_word_models: LanguageDetector,
+    ) {
+        let word_probabilities = detector_for_english_and_german_with_word_models
+            .compute_word_probabilities(
+                &[("the alter xyz".to_string(), hashset!(English, German))],
+                &hashset!(English, German),
+                &hashmap!(English => 10, German => 8),
+            );
+
+        let expected_probability_for_english =
+            (0.1_f64.ln() + 0.05_f64.ln() + 0.05_f64.ln()) / 10.0;
+        let expected_probability_for_german = (0.01_f64.ln() + 0.1_f64.ln() + 0.01_f64.ln()) / 8.0;
+
+        assert!(approx_eq!(
+            f64,
+            word_probabilities[&English],
+            expected_probability_for_english,
+            ulps = 1
+        ));
+        assert!(approx_eq!(
+            f64,
+            word_probabilities[&German],
+            expected_probability_for_german,
+            ulps = 1
+        ));
+    }
+
+    #[rstest(
+        text,
+        expected_language,
+        case("the house is on the hill", English),
+        case("das haus ist auf dem hügel", German)
+    )]
+    fn assert_real_word_models_favor_the_right_language(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        expected_language: Language,
+    ) {
+        let detector = LanguageDetector {
+            word_language_models: word_models(),
+            ..detector_for_english_and_german
+        };
+        let word_probabilities = detector.compute_word_probabilities(
+            &[(text.to_string(), hashset!(English, German))],
+            &hashset!(English, German),
+            &hashmap!(English => 12, German => 12),
+        );
+        let most_likely_language = word_probabilities
+            .iter()
+            .max_by(|(_, first), (_, second)| first.partial_cmp(second).unwrap())
+            .map(|(language, _)| language.clone());
+
+        assert_eq!(most_likely_language, Some(expected_language));
+    }
+
+    #[rstest(
+        text,
+        languages,
+        unigram_counts,
+        case::no_known_words(
+            "xyz",
+            hashset!(English, German),
+            hashmap!(English => 3, German => 3)
+        ),
+        case::language_without_word_model(
+            "the",
+            hashset!(English, French),
+            hashmap!(English => 3, French => 3)
+        ),
+        case::language_without_unigram_count(
+            "the",
+            hashset!(English, German),
+            hashmap!(English => 3)
+        )
+    )]
+    fn assert_word_probabilities_are_not_computed_without_sufficient_word_models(
+        detector_for_english_and_german_with_word_models: LanguageDetector,
+        text: &str,
+        languages: HashSet<Language>,
+        unigram_counts: HashMap<Language, u32>,
+    ) {
+        let word_probabilities = detector_for_english_and_german_with_word_models
+            .compute_word_probabilities(
+                &[(text.to_string(), languages.clone())],
+                &languages,
+                &unigram_counts,
+            );
+
+        assert!(word_probabilities.is_empty());
+    }
+
+    #[rstest]
+    fn assert_word_probabilities_are_added_to_confidence_values(
+        detector_for_english_and_german: LanguageDetector,
+        detector_for_english_and_german_with_word_models: LanguageDetector,
+    ) {
+        let confidence_values_without_word_models =
+            detector_for_english_and_german.compute_language_confidence_values("Alter");
+        let confidence_values = detector_for_english_and_german_with_word_models
+            .compute_language_confidence_values("Alter");
+
+        assert_eq!(confidence_values[0].0, German);
+        assert_eq!(confidence_values[1].0, English);
+        assert!(confidence_values[1].1 < confidence_values_without_word_models[1].1);
+    }
+
+    #[rstest(
+        words,
+        confidence_values,
+        expected_confidence_values,
+        case::decided_by_word(
+            vec!["što", "je"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98), (English, 0.5)],
+            vec![(Croatian, 1.0), (Bosnian, 0.98), (English, 0.5)]
+        ),
+        case::decided_by_ngram(
+            vec!["netko"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98)],
+            vec![(Croatian, 1.0), (Bosnian, 0.98)]
+        ),
+        case::confirmed_by_word(
+            vec!["šta"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98)],
+            vec![(Bosnian, 1.0), (Croatian, 0.98)]
+        ),
+        case::balanced_evidence(
+            vec!["kuća"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98)],
+            vec![(Bosnian, 1.0), (Croatian, 0.98)]
+        ),
+        case::missing_pairwise_model(
+            vec!["što"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98), (Serbian, 0.97)],
+            vec![(Bosnian, 1.0), (Croatian, 0.98), (Serbian, 0.97)]
+        ),
+        case::clear_winner(
+            vec!["što", "je"],
+            vec![(Bosnian, 1.0), (Croatian, 0.9), (English, 0.5)],
+            vec![(Bosnian, 1.0), (Croatian, 0.9), (English, 0.5)]
+        ),
+        case::outside_of_margin(
+            vec!["što"],
+            vec![(Bosnian, 1.0), (Croatian, 0.98), (Serbian, 0.9)],
+            vec![(Croatian, 1.0), (Bosnian, 0.98), (Serbian, 0.9)]
+        ),
+        case::no_cluster(
+            vec!["što"],
+            vec![(Bosnian, 1.0), (English, 0.98), (Croatian, 0.97)],
+            vec![(Bosnian, 1.0), (English, 0.98), (Croatian, 0.97)]
+        )
+    )]
+    fn assert_confusable_languages_are_disambiguated_correctly(
+        detector_for_english_and_german_with_pairwise_models: LanguageDetector,
+        words: Vec<&str>,
+        confidence_values: Vec<(Language, f64)>,
+        expected_confidence_values: Vec<(Language, f64)>,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german_with_pairwise_models
+                .disambiguate_confusable_languages(&words, confidence_values),
+            expected_confidence_values
+        );
+    }
+
+    #[rstest(
+        confidence_values,
+        expected_result,
+        case::single_language(
+            vec![(Bokmal, 1.0), (Nynorsk, 0.8)],
+            Some(LanguageOrGroup::Language(Bokmal))
+        ),
+        case::norwegian(
+            vec![(Bokmal, 1.0), (Nynorsk, 0.95), (Danish, 0.8)],
+            Some(LanguageOrGroup::Group(LanguageGroup::Norwegian))
+        ),
+        case::south_slavic_latin(
+            vec![(Croatian, 1.0), (Bosnian, 1.0), (Serbian, 0.91), (Slovene, 0.7)],
+            Some(LanguageOrGroup::Group(LanguageGroup::SouthSlavicLatin))
+        ),
+        case::mixed_groups(
+            vec![(Bokmal, 1.0), (Danish, 0.95), (Nynorsk, 0.93)],
+            None
+        ),
+        case::no_group(vec![(English, 1.0), (German, 0.95)], None),
+        case::no_values(vec![], None)
+    )]
+    fn assert_language_or_group_is_selected_correctly(
+        detector_for_english_and_german: LanguageDetector,
+        confidence_values: Vec<(Language, f64)>,
+        expected_result: Option<LanguageOrGroup>,
+    ) {
+        let detector = LanguageDetector {
+            minimum_relative_distance: 0.1,
+            ..detector_for_english_and_german
+        };
+        assert_eq!(
+            detector.select_most_likely_language_or_group(&confidence_values),
+            expected_result
+        );
+    }
+
+    #[rstest]
+    fn assert_input_text_is_transliterated_for_models_of_other_scripts(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let input_texts = detector_for_english_and_german
+            .transliterate_input_text(
+                "ljubav",
+                Some(Alphabet::Latin),
+                &hashset!(Bosnian, Croatian, Serbian),
+            )
+            .into_iter()
+            .sorted_by(|(first, _), (second, _)| first.cmp(second))
+            .collect_vec();
+
+        assert_eq!(
+            input_texts,
+            vec![
+                ("ljubav".to_string(), hashset!(Bosnian, Croatian)),
+                ("љубав".to_string(), hashset!(Serbian))
+            ]
+        );
+    }
+
+    #[rstest(
+        languages,
+        expected_languages,
+        case(hashset!(English, Serbian), hashset!(English, Serbian)),
+        case(hashset!(Croatian, English, Serbian), hashset!(Croatian, English)),
+        case(hashset!(Bosnian, English, Serbian), hashset!(Bosnian, English))
+    )]
+    fn assert_serbian_latin_is_left_to_native_models_of_close_relatives(
+        detector_for_all_languages: LanguageDetector,
+        languages: HashSet<Language>,
+        expected_languages: HashSet<Language>,
+    ) {
+        let detector = LanguageDetector {
+            languages,
+            text_preprocessors: vec![],
+            languages_with_unique_characters: hashset!(),
+            one_language_alphabets: hashmap!(),
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.filter_languages_by_rules(&["ljubav"]),
+            expected_languages
+        );
+    }
+
+    #[rstest]
+    fn assert_romanized_input_text_is_converted_into_native_script(
+        detector_for_all_languages: LanguageDetector,
+    ) {
+        let detector = LanguageDetector {
+            include_romanized_languages: true,
+            ..detector_for_all_languages
+        };
+        let input_texts = detector
+            .transliterate_input_text(
+                "privet kak dela",
+                Some(Alphabet::Latin),
+                &hashset!(English, Russian),
+            )
+            .into_iter()
+            .sorted_by(|(first, _), (second, _)| first.cmp(second))
+            .collect_vec();
+
+        assert_eq!(
+            input_texts,
+            vec![
+                ("privet kak dela".to_string(), hashset!(English)),
+                ("привет как дела".to_string(), hashset!(Russian))
+            ]
+        );
+    }
+
+    #[rstest(
+        include_romanized_languages,
+        word,
+        expected_languages,
+        case(false, "privet", hashset!(English, German)),
+        case(true, "privet", hashset!(English, German, Russian)),
+        case::implausible_romanization(true, "the", hashset!(English, German))
+    )]
+    fn assert_plausibly_romanized_languages_are_not_filtered_out_by_rules(
+        detector_for_all_languages: LanguageDetector,
+        include_romanized_languages: bool,
+        word: &str,
+        expected_languages: HashSet<Language>,
+    ) {
+        static UNIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static BIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static TRIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static ENGLISH_UNIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static ENGLISH_BIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static ENGLISH_TRIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static RUSSIAN_UNIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static RUSSIAN_BIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static RUSSIAN_TRIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+
+        let detector = LanguageDetector {
+            languages: hashset!(English, German, Russian),
+            include_romanized_languages,
+            unigram_language_models: UNIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(
+                    English => ENGLISH_UNIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "p" => 0.01, "r" => 0.01, "i" => 0.01, "v" => 0.01, "e" => 0.01,
+                            "t" => 0.01
+                        ))
+                    }),
+                    Russian => RUSSIAN_UNIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!("т" => 0.01))
+                    })
+                )
+            }),
+            bigram_language_models: BIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(
+                    English => ENGLISH_BIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "pr" => 0.0, "ri" => 0.0, "iv" => 0.0, "ve" => 0.0, "et" => 0.0
+                        ))
+                    }),
+                    Russian => RUSSIAN_BIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!("тх" => 0.0))
+                    })
+                )
+            }),
+            trigram_language_models: TRIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(
+                    English => ENGLISH_TRIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "pri" => 0.0, "riv" => 0.0, "ive" => 0.0, "vet" => 0.0,
+                            "the" => 0.1
+                        ))
+                    }),
+                    Russian => RUSSIAN_TRIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "при" => 0.1, "рив" => 0.1, "иве" => 0.1, "вет" => 0.1,
+                            "тхе" => 0.0
+                        ))
+                    })
+                )
+            }),
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.filter_languages_by_rules(&[word]),
+            expected_languages
+        );
+    }
+
+    #[rstest(
+        correct_keyboard_layouts,
+        expected_corrected_text,
+        case(false, None),
+        case(true, Some("Привет".to_string()))
+    )]
+    fn assert_text_typed_with_wrong_keyboard_layout_is_corrected(
+        detector_for_english_and_german: LanguageDetector,
+        correct_keyboard_layouts: bool,
+        expected_corrected_text: Option<String>,
+    ) {
+        static UNIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static BIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static TRIGRAM_MODELS_FIXTURE: LanguageToNgramsMappingCell = OnceCell::new();
+        static ENGLISH_UNIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static ENGLISH_BIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static ENGLISH_TRIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+        static RUSSIAN_TRIGRAM_MODEL_FIXTURE: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+
+        let detector = LanguageDetector {
+            languages: hashset!(English, Russian),
+            correct_keyboard_layouts,
+            unigram_language_models: UNIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(English => ENGLISH_UNIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                    create_training_model_mock(hashmap!(
+                        "g" => 0.01, "h" => 0.01, "b" => 0.01, "d" => 0.01
+                    ))
+                }))
+            }),
+            bigram_language_models: BIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(English => ENGLISH_BIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                    create_training_model_mock(hashmap!(
+                        "gh" => 0.0, "hb" => 0.0, "bd" => 0.0, "dt" => 0.0
+                    ))
+                }))
+            }),
+            trigram_language_models: TRIGRAM_MODELS_FIXTURE.get_or_init(|| {
+                hashmap!(
+                    English => ENGLISH_TRIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "ghb" => 0.0, "hbd" => 0.0, "bdt" => 0.0, "dtn" => 0.0
+                        ))
+                    }),
+                    Russian => RUSSIAN_TRIGRAM_MODEL_FIXTURE.get_or_init(|| {
+                        create_training_model_mock(hashmap!(
+                            "при" => 0.1, "рив" => 0.1, "иве" => 0.1, "вет" => 0.1
+                        ))
+                    })
+                )
+            }),
+            ..detector_for_english_and_german
+        };
+
+        assert_eq!(
+            detector.correct_keyboard_layout("Ghbdtn", None),
+            expected_corrected_text
+        );
+    }
+
+    #[rstest]
+    fn assert_capitalized_words_known_to_word_models_are_not_down_weighted(
+        detector_for_english_and_german_with_word_models: LanguageDetector,
+    ) {
+        let detector = LanguageDetector {
+            down_weight_proper_nouns: true,
+            ..detector_for_english_and_german_with_word_models
+        };
+
+        assert_eq!(
+            detector.find_down_weighted_words("Das Alter von Anna"),
+            vec!["Anna"]
+        );
+    }
+
+    #[rstest(
+        down_weight_proper_nouns,
+        expected_words,
+        expected_positions,
+        case(false, vec![], hashset!()),
+        case(true, vec!["Hallo", "Anna"], hashset!(1, 3))
+    )]
+    fn assert_proper_nouns_are_down_weighted(
+        detector_for_all_languages: LanguageDetector,
+        down_weight_proper_nouns: bool,
+        expected_words: Vec<&str>,
+        expected_positions: HashSet<usize>,
+    ) {
+        let detector = LanguageDetector {
+            down_weight_proper_nouns,
+            text_preprocessors: vec![Arc::new(MentionFilter)],
+            ..detector_for_all_languages
+        };
+        let down_weighted_words = detector.find_down_weighted_words("@Jane sagt Hallo zu Anna.");
+
+        assert_eq!(down_weighted_words, expected_words);
+        assert_eq!(
+            detector.find_down_weighted_positions(
+                &["sagt", "hallo", "zu", "anna"],
+                &down_weighted_words
+            ),
+            expected_positions
+        );
+    }
+
+    #[rstest]
+    fn assert_ngrams_of_down_weighted_words_are_found(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german.find_down_weighted_ngrams(
+                "haus hausmann",
+                &hashset!(1),
+                2
+            ),
+            hashset!(
+                Ngram::new("sm"),
+                Ngram::new("ma"),
+                Ngram::new("an"),
+                Ngram::new("nn")
+            )
+        );
+    }
+
+    #[rstest(
+        normalize_homoglyphs,
+        expected_text,
+        case(false, None),
+        case(true, Some("paypal login".to_string()))
+    )]
+    fn assert_mixed_script_words_are_normalized(
+        detector_for_all_languages: LanguageDetector,
+        normalize_homoglyphs: bool,
+        expected_text: Option<String>,
+    ) {
+        let detector = LanguageDetector {
+            normalize_homoglyphs,
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.normalize_mixed_script_words("pаypаl login"),
+            expected_text
+        );
+    }
+
+    #[rstest]
+    fn assert_detection_result_reports_normalized_homoglyphs(
+        detector_for_all_languages: LanguageDetector,
+    ) {
+        let detector = LanguageDetector {
+            normalize_homoglyphs: true,
+            ..detector_for_all_languages
+        };
+        let result = detector
+            .detect_language_with_details_of("məhаrrəm")
+            .unwrap();
+
+        assert_eq!(result.language(), &Azerbaijani);
+        assert!(result.has_normalized_homoglyphs());
+    }
+
+    #[rstest(
+        text,
+        expected_language,
+        expected_language_tag,
+        case("наслеђивања", Serbian, "sr-Cyrl"),
+        case("məhərrəm", Azerbaijani, "az-Latn"),
+        case("ภาษาไทย", Thai, "th-Thai"),
+        case("这是一个问题", Chinese, "zh-Hans"),
+        case("這是一個問題", Chinese, "zh-Hant"),
+        case("中文", Chinese, "zh-Hani")
+    )]
+    fn assert_detection_result_contains_script(
+        detector_for_all_languages: LanguageDetector,
+        text: &str,
+        expected_language: Language,
+        expected_language_tag: &str,
+    ) {
+        let result = detector_for_all_languages
+            .detect_language_with_details_of(text)
+            .unwrap();
+
+        assert_eq!(result.language(), &expected_language);
+        assert_eq!(result.language_tag(), expected_language_tag);
+    }
+
+    #[rstest(
+        sampling_strategy,
+        expected_text,
+        case(None, "ภาษาไทย ภาษาไทย ภาษาไทย 这是一个问题"),
+        case(Some(SamplingStrategy::FirstCharacters(17)), "ภาษาไทย ภาษาไทย"),
+        case(
+            Some(SamplingStrategy::EvenlySpacedWindows {
+                window_count: 2,
+                window_length: 10
+            }),
+            "ภาษาไทย 这是一个问题"
         )
     )]
-    fn assert_summation_of_ngram_probabilities_works_correctly(
-        detector_for_english_and_german: LanguageDetector,
-        ngrams: HashSet<&str>,
-        expected_sum_of_probabilities: f64,
+    fn assert_text_is_sampled_if_desired(
+        detector_for_all_languages: LanguageDetector,
+        sampling_strategy: Option<SamplingStrategy>,
+        expected_text: &str,
     ) {
-        let mapped_ngrams = ngrams
-            .iter()
-            .map(|&it| Ngram::new(it))
-            .collect::<HashSet<_>>();
-
-        let sum_of_probabilities = detector_for_english_and_german
-            .compute_sum_of_ngram_probabilities(&English, &mapped_ngrams);
+        let detector = LanguageDetector {
+            sampling_strategy,
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.sample_text("ภาษาไทย ภาษาไทย ภาษาไทย 这是一个问题".to_string()),
+            expected_text
+        );
+    }
 
-        assert!(
-            approx_eq!(
-                f64,
-                sum_of_probabilities,
-                expected_sum_of_probabilities,
-                ulps = 1
-            ),
-            "expected sum {} for language '{:?}' and ngrams {:?}, got {}",
-            expected_sum_of_probabilities,
-            English,
-            ngrams,
-            sum_of_probabilities
+    #[rstest(
+        check_sample_consistency,
+        expected_sample_agreement,
+        case(false, None),
+        case(true, Some(0.5))
+    )]
+    fn assert_sample_agreement_is_computed_if_desired(
+        detector_for_all_languages: LanguageDetector,
+        check_sample_consistency: bool,
+        expected_sample_agreement: Option<f64>,
+    ) {
+        let detector = LanguageDetector {
+            sampling_strategy: Some(SamplingStrategy::EvenlySpacedWindows {
+                window_count: 2,
+                window_length: 10,
+            }),
+            check_sample_consistency,
+            ..detector_for_all_languages
+        };
+        assert_eq!(
+            detector.compute_sample_agreement("ภาษาไทย ภาษาไทย ภาษาไทย 这是一个问题", &Thai, None),
+            expected_sample_agreement
         );
     }
 
     #[rstest(
-        test_data_model,
-        expected_probabilities,
-        case::unigram_model(
-            test_data_model(hashset!("a", "l", "t", "e", "r")),
-            hashmap!(
-                English => 0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln(),
-                German => 0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln()
-            )
-        ),
-        case::trigram_model(
-            test_data_model(hashset!("alt", "lte", "ter", "wxy")),
-            hashmap!(
-                English => 0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln(),
-                German => 0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln()
-            )
-        ),
-        case::quadrigram_model(
-            test_data_model(hashset!("alte", "lter", "wxyz")),
-            hashmap!(
-                English => 0.25_f64.ln() + 0.26_f64.ln(),
-                German => 0.27_f64.ln() + 0.28_f64.ln()
-            )
-        )
+        budget,
+        expected_language,
+        case(Duration::from_secs(60), Some(German)),
+        case(Duration::from_secs(0), None)
     )]
-    fn assert_computation_of_language_probabilities_works_correctly(
+    fn assert_language_is_detected_within_budget(
         detector_for_english_and_german: LanguageDetector,
-        test_data_model: TestDataLanguageModel,
-        expected_probabilities: HashMap<Language, f64>,
+        budget: Duration,
+        expected_language: Option<Language>,
     ) {
-        let probabilities = detector_for_english_and_german
-            .compute_language_probabilities(&test_data_model, &hashset!(English, German));
+        let result = detector_for_english_and_german.detect_language_of_within("Alter", budget);
 
-        for (language, probability) in probabilities {
-            let expected_probability = expected_probabilities[&language];
+        assert_eq!(
+            result.as_ref().map(|it| it.language().clone()),
+            expected_language
+        );
+        assert!(result.map_or(true, |it| !it.is_partial()));
+    }
 
-            assert!(
-                approx_eq!(f64, probability, expected_probability, ulps = 1),
-                "expected probability {} for language '{:?}', got {}",
-                expected_probability,
-                language,
-                probability
-            );
-        }
+    #[rstest]
+    fn assert_rules_detect_language_without_budget(detector_for_all_languages: LanguageDetector) {
+        let result = detector_for_all_languages
+            .detect_language_of_within("ภาษาไทย", Duration::from_secs(0))
+            .unwrap();
+
+        assert_eq!(result.language(), &Thai);
+        assert!(!result.is_partial());
     }
 
     #[rstest]
-    fn assert_computation_of_confidence_values_works_correctly(
+    fn assert_optional_stages_are_skipped_without_budget(
         detector_for_english_and_german: LanguageDetector,
     ) {
-        let unigram_count_for_both_languages = 5.0;
-
-        let total_probability_for_german = (
-            // unigrams
-            0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln() +
-            // bigrams
-            0.15_f64.ln() + 0.16_f64.ln() + 0.17_f64.ln() + 0.18_f64.ln() +
-            // trigrams
-            0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln() +
-            // quadrigrams
-            0.27_f64.ln() + 0.28_f64.ln() +
-            // fivegrams
-            0.3_f64.ln()
-        ) / unigram_count_for_both_languages;
-
-        let total_probability_for_english = (
-            // unigrams
-            0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln() +
-            // bigrams
-            0.11_f64.ln() + 0.12_f64.ln() + 0.13_f64.ln() + 0.14_f64.ln() +
-            // trigrams
-            0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln() +
-            // quadrigrams
-            0.25_f64.ln() + 0.26_f64.ln() +
-            // fivegrams
-            0.29_f64.ln()
-        ) / unigram_count_for_both_languages;
-
-        let expected_confidence_for_german = 1.0;
-        let expected_confidence_for_english =
-            total_probability_for_german / total_probability_for_english;
-
-        let confidence_values =
-            detector_for_english_and_german.compute_language_confidence_values("Alter");
+        let detector = LanguageDetector {
+            correct_keyboard_layouts: true,
+            normalize_homoglyphs: true,
+            sampling_strategy: Some(SamplingStrategy::EvenlySpacedWindows {
+                window_count: 2,
+                window_length: 6,
+            }),
+            check_sample_consistency: true,
+            ..detector_for_english_and_german
+        };
 
         assert_eq!(
-            confidence_values[0],
-            (German, expected_confidence_for_german)
+            detector.correct_keyboard_layout("Ghbdtn", Some(Instant::now())),
+            None
         );
-        assert_eq!(confidence_values[1].0, English);
-        assert!(approx_eq!(
-            f64,
-            confidence_values[1].1,
-            expected_confidence_for_english,
-            ulps = 1
-        ));
+        assert_eq!(
+            detector.compute_sample_agreement("Alter Alter Alter", &German, Some(Instant::now())),
+            None
+        );
+        assert_eq!(
+            detector.detect_language_of_within("Ghbdtn Alter Alter", Duration::from_secs(0)),
+            None
+        );
+    }
+
+    #[rstest]
+    fn assert_confidence_values_are_partial_after_deadline(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let (confidence_values, is_partial) = detector_for_english_and_german
+            .compute_confidence_values_until("alter".to_string(), &[], Some(Instant::now()));
+
+        assert!(confidence_values.is_empty());
+        assert!(is_partial);
     }
 
     #[rstest]
@@ -1077,8 +3312,6 @@ mod tests {
         case("fekvő", Some(Hungarian)),
         case("meggyűrűzni", Some(Hungarian)),
         case("ヴェダイヤモンド", Some(Japanese)),
-        case("әлем", Some(Kazakh)),
-        case("шаруашылығы", Some(Kazakh)),
         case("ақын", Some(Kazakh)),
         case("оның", Some(Kazakh)),
         case("шұрайлы", Some(Kazakh)),
@@ -1094,8 +3327,6 @@ mod tests {
         case("ќерка", Some(Macedonian)),
         case("џамиите", Some(Macedonian)),
         case("मिळते", Some(Marathi)),
-        case("үндсэн", Some(Mongolian)),
-        case("дөхөж", Some(Mongolian)),
         case("zmieniły", Some(Polish)),
         case("państwowych", Some(Polish)),
         case("mniejszości", Some(Polish)),
@@ -1157,23 +3388,36 @@ mod tests {
         case("mỵ", Some(Vietnamese)),
         case("aṣiwèrè", Some(Yoruba)),
         case("ṣaaju", Some(Yoruba)),
+        case("بڑے", Some(Urdu)),
+        case("پاکستانیوں", Some(Urdu)),
         case("والموضوع", None),
+        case("پنجره", None),
         case("сопротивление", None),
         case("house", None),
 
         // words with unique alphabet
+        case("ኢትዮጵያ", Some(Amharic)),
         case("ունենա", Some(Armenian)),
         case("জানাতে", Some(Bengali)),
+        case("မြန်မာ", Some(Burmese)),
+        case("ދިވެހި", Some(Dhivehi)),
         case("გარეუბან", Some(Georgian)),
         case("σταμάτησε", Some(Greek)),
         case("ઉપકરણોની", Some(Gujarati)),
         case("בתחרויות", Some(Hebrew)),
         case("びさ", Some(Japanese)),
+        case("ಕನ್ನಡ", Some(Kannada)),
+        case("ភាសាខ្មែរ", Some(Khmer)),
         case("대결구도가", Some(Korean)),
+        case("ພາສາລາວ", Some(Lao)),
+        case("മലയാളം", Some(Malayalam)),
+        case("ଓଡ଼ିଆ", Some(Odia)),
         case("ਮੋਟਰਸਾਈਕਲਾਂ", Some(Punjabi)),
+        case("සිංහල", Some(Sinhala)),
         case("துன்பங்களை", Some(Tamil)),
         case("కృష్ణదేవరాయలు", Some(Telugu)),
         case("ในทางหลวงหมายเลข", Some(Thai)),
+        case("བོད", Some(Tibetan)),
     )]
     fn assert_language_detection_with_rules_works_correctly(
         detector_for_all_languages: LanguageDetector,
@@ -1188,23 +3432,48 @@ mod tests {
         );
     }
 
+    #[rstest(
+        text,
+        expected_language,
+        case("这是一个很长的中文句子 ok", Some(Chinese)),
+        case("東京に行きます", Some(Japanese)),
+        case("ok 这是", None)
+    )]
+    fn assert_rule_votes_are_weighted_by_character_counts(
+        detector_for_all_languages: LanguageDetector,
+        text: &str,
+        expected_language: Option<Language>,
+    ) {
+        let words = detector_for_all_languages.split_text_into_words(text);
+        assert_eq!(
+            detector_for_all_languages.detect_language_with_rules(&words),
+            expected_language
+        );
+    }
+
     #[rstest(word, expected_languages,
         case("والموضوع", hashset!(Arabic, Persian, Urdu)),
+        case("پنجره", hashset!(Persian, Urdu)),
+        case("گزارش", hashset!(Persian, Urdu)),
         case(
             "сопротивление",
             hashset!(
-                Belarusian, Bulgarian, Kazakh, Macedonian, Mongolian, Russian, Serbian, Ukrainian
+                Azerbaijani, Belarusian, Bulgarian, Kazakh, Macedonian, Mongolian, Russian,
+                Serbian, Ukrainian
             )
         ),
-        case("раскрывае", hashset!(Belarusian, Kazakh, Mongolian, Russian)),
+        case("раскрывае", hashset!(Azerbaijani, Belarusian, Kazakh, Mongolian, Russian)),
         case("этот", hashset!(Belarusian, Kazakh, Mongolian, Russian)),
         case("огнём", hashset!(Belarusian, Kazakh, Mongolian, Russian)),
         case("плаваща", hashset!(Bulgarian, Kazakh, Mongolian, Russian)),
         case("довършат", hashset!(Bulgarian, Kazakh, Mongolian, Russian)),
         case("павінен", hashset!(Belarusian, Kazakh, Ukrainian)),
         case("затоплување", hashset!(Macedonian, Serbian)),
-        case("ректасцензија", hashset!(Macedonian, Serbian)),
+        case("ректасцензија", hashset!(Azerbaijani, Macedonian, Serbian)),
         case("набљудувач", hashset!(Macedonian, Serbian)),
+        case("әлем", hashset!(Azerbaijani, Kazakh)),
+        case("дөхөж", hashset!(Azerbaijani, Kazakh, Mongolian)),
+        case("үлкен", hashset!(Azerbaijani, Kazakh, Mongolian)),
         case("aizklātā", hashset!(Latvian, Maori, Yoruba)),
         case("sistēmas", hashset!(Latvian, Maori, Yoruba)),
         case("palīdzi", hashset!(Latvian, Maori, Yoruba)),
@@ -1246,9 +3515,18 @@ mod tests {
         ),
         case("indebærer", hashset!(Bokmal, Danish, Icelandic, Nynorsk)),
         case("måned", hashset!(Bokmal, Danish, Nynorsk, Swedish)),
-        case("zaručen", hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)),
-        case("zkouškou", hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)),
-        case("navržen", hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)),
+        case(
+            "zaručen",
+            hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)
+        ),
+        case(
+            "zkouškou",
+            hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)
+        ),
+        case(
+            "navržen",
+            hashset!(Bosnian, Czech, Croatian, Latvian, Lithuanian, Slovak, Slovene)
+        ),
         case(
             "façonnage",
             hashset!(Albanian, Azerbaijani, Basque, Catalan, French, Latvian, Portuguese, Turkish)
@@ -1308,7 +3586,7 @@ mod tests {
         word: &str,
         expected_languages: HashSet<Language>,
     ) {
-        let filtered_languages = detector_for_all_languages.filter_languages_by_rules(vec![word]);
+        let filtered_languages = detector_for_all_languages.filter_languages_by_rules(&[word]);
         assert_eq!(
             filtered_languages, expected_languages,
             "expected {:?} for word '{}', got {:?}",
@@ -1316,6 +3594,21 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn assert_alphabet_votes_are_weighted_by_character_count(
+        detector_for_all_languages: LanguageDetector,
+    ) {
+        let words = ["сопротивление", "so", "to", "be"];
+
+        assert_eq!(
+            detector_for_all_languages.detect_alphabet(&words),
+            Some(Alphabet::Cyrillic)
+        );
+        assert!(detector_for_all_languages
+            .filter_languages_by_rules(&words)
+            .contains(&Russian));
+    }
+
     #[rstest(invalid_str, case(""), case(" \n  \t;"), case("3<856%)§"))]
     fn assert_strings_without_letters_return_no_language(
         detector_for_all_languages: LanguageDetector,
@@ -1326,4 +3619,237 @@ mod tests {
             None
         );
     }
+
+    #[rstest]
+    fn assert_document_fields_are_combined_by_weight_and_length(
+        detector_for_all_languages: LanguageDetector,
+    ) {
+        let greek = "Καλημέρα";
+        let thai = "ในทางหลวงหมายเลข";
+
+        let result =
+            detector_for_all_languages.detect_language_of_document(&[(greek, 1.0), (thai, 1.0)]);
+        assert_eq!(result.language(), Some(&Thai));
+        assert_eq!(result.field_languages(), &[Some(Greek), Some(Thai)]);
+        assert_eq!(result.field_confidence_values()[0], vec![(Greek, 1.0)]);
+        assert!(result.has_disagreeing_fields());
+
+        let result =
+            detector_for_all_languages.detect_language_of_document(&[(greek, 3.0), (thai, 1.0)]);
+        assert_eq!(result.language(), Some(&Greek));
+        assert_eq!(result.confidence_values()[0], (Greek, 1.0));
+        assert_eq!(result.confidence_values()[1].0, Thai);
+
+        let result =
+            detector_for_all_languages.detect_language_of_document(&[(greek, 0.0), (thai, 1.0)]);
+        assert_eq!(result.language(), Some(&Thai));
+    }
+
+    #[rstest]
+    fn assert_document_fields_without_letters_are_ignored(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let result = detector_for_english_and_german
+            .detect_language_of_document(&[("Alter", 1.0), ("3 < 4", 2.0)]);
+
+        assert_eq!(result.language(), Some(&German));
+        assert_eq!(result.confidence_values()[0], (German, 1.0));
+        assert_eq!(result.field_languages(), &[Some(German), None]);
+        assert!(result.field_confidence_values()[1].is_empty());
+        assert!(!result.has_disagreeing_fields());
+    }
+
+    #[rstest(
+        weight,
+        case::negative(-1.0),
+        case::not_a_number(f64::NAN),
+        case::infinite(f64::INFINITY)
+    )]
+    #[should_panic(expected = "field weights must be finite and not negative")]
+    fn assert_document_fields_must_have_valid_weights(
+        detector_for_english_and_german: LanguageDetector,
+        weight: f64,
+    ) {
+        detector_for_english_and_german
+            .detect_language_of_document(&[("Alter", 1.0), ("Alter", weight)]);
+    }
+
+    #[rstest]
+    fn assert_document_without_fields_returns_no_language(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let result = detector_for_english_and_german.detect_language_of_document::<&str>(&[]);
+
+        assert_eq!(result.language(), None);
+        assert!(result.confidence_values().is_empty());
+        assert!(result.field_languages().is_empty());
+    }
+
+    #[rstest]
+    fn assert_language_verification_compares_with_strongest_competitor(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let german_verification = detector_for_english_and_german.verify_language("Alter", German);
+        let english_verification =
+            detector_for_english_and_german.verify_language("Alter", English);
+
+        assert!(german_verification.is_verified());
+        assert_eq!(german_verification.strongest_competitor(), Some(&English));
+        assert!(!german_verification.is_ruled_out_by_rules());
+
+        assert!(!english_verification.is_verified());
+        assert_eq!(english_verification.strongest_competitor(), Some(&German));
+        assert!(german_verification.score() > english_verification.score());
+        assert!(approx_eq!(
+            f64,
+            german_verification.margin().unwrap(),
+            -english_verification.margin().unwrap(),
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest(
+        text,
+        language,
+        expected_competitor,
+        case("Καλημέρα", German, Some(Greek)),
+        case("сопротивление", German, None)
+    )]
+    fn assert_language_verification_is_short_circuited_by_rules(
+        detector_for_all_languages: LanguageDetector,
+        text: &str,
+        language: Language,
+        expected_competitor: Option<Language>,
+    ) {
+        let verification = detector_for_all_languages.verify_language(text, language);
+
+        assert!(!verification.is_verified());
+        assert!(verification.is_ruled_out_by_rules());
+        assert_eq!(verification.score(), 0.0);
+        assert_eq!(verification.margin(), None);
+        assert_eq!(
+            verification.strongest_competitor(),
+            expected_competitor.as_ref()
+        );
+    }
+
+    #[rstest]
+    fn assert_language_decided_by_rules_is_verified(detector_for_all_languages: LanguageDetector) {
+        let verification = detector_for_all_languages.verify_language("ในทางหลวงหมายเลข", Thai);
+
+        assert!(verification.is_verified());
+        assert!(verification.score() > 0.95);
+        assert!(!verification.is_ruled_out_by_rules());
+    }
+
+    #[rstest(text, language, case("3 < 4", German), case("Alter", French))]
+    fn assert_language_verification_fails_without_evidence(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        language: Language,
+    ) {
+        let verification = detector_for_english_and_german.verify_language(text, language);
+
+        assert!(!verification.is_verified());
+        assert_eq!(verification.score(), 0.0);
+        assert_eq!(verification.strongest_competitor(), None);
+    }
+
+    #[rstest]
+    fn assert_confidence_values_are_cached_by_cleaned_up_text(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let mut detector = LanguageDetector {
+            cache: Some(Arc::new(ResultCache::new(10))),
+            ..detector_for_english_and_german
+        };
+        detector.cache_fingerprint = detector.compute_cache_fingerprint();
+        let confidence_values = detector.compute_language_confidence_values("Alter");
+
+        assert_eq!(confidence_values[0], (German, 1.0));
+        assert_eq!(
+            detector.compute_language_confidence_values("  Alter!"),
+            confidence_values
+        );
+        assert_eq!(detector.detect_language_of("Alter"), Some(German));
+
+        let statistics = detector.cache_statistics().unwrap();
+
+        assert_eq!(statistics.hits(), 2);
+        assert_eq!(statistics.misses(), 1);
+        assert_eq!(statistics.len(), 1);
+    }
+
+    #[rstest]
+    fn assert_cache_is_shared_by_detectors_with_different_options(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let cache = Arc::new(ResultCache::new(10));
+        let mut english_detector = LanguageDetector {
+            languages: hashset!(English),
+            cache: Some(cache.clone()),
+            text_preprocessors: vec![],
+            languages_with_unique_characters: hashset!(),
+            one_language_alphabets: hashmap!(),
+            ..detector_for_english_and_german
+        };
+        english_detector.cache_fingerprint = english_detector.compute_cache_fingerprint();
+
+        let mut detector = LanguageDetector {
+            cache: Some(cache.clone()),
+            ..detector_for_english_and_german
+        };
+        detector.cache_fingerprint = detector.compute_cache_fingerprint();
+
+        let mut distant_detector = LanguageDetector {
+            languages: hashset!(English, German),
+            minimum_relative_distance: 0.1,
+            cache: Some(cache.clone()),
+            text_preprocessors: vec![],
+            languages_with_unique_characters: hashset!(),
+            one_language_alphabets: hashmap!(),
+            ..detector_for_english_and_german
+        };
+        distant_detector.cache_fingerprint = distant_detector.compute_cache_fingerprint();
+
+        assert_eq!(detector.detect_language_of("Alter"), Some(German));
+        assert_eq!(english_detector.detect_language_of("Alter"), Some(English));
+        assert_eq!(distant_detector.detect_language_of("Alter"), Some(German));
+        assert_eq!(cache.statistics().misses(), 3);
+        assert_eq!(cache.statistics().len(), 3);
+    }
+
+    #[rstest]
+    fn assert_nothing_is_cached_with_preprocessor_without_id(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        struct Identity;
+
+        impl TextPreprocessor for Identity {
+            fn preprocess(&self, text: &str) -> String {
+                text.to_string()
+            }
+        }
+
+        let cache = Arc::new(ResultCache::new(10));
+        let mut detector = LanguageDetector {
+            cache: Some(cache.clone()),
+            text_preprocessors: vec![Arc::new(Identity)],
+            ..detector_for_english_and_german
+        };
+        detector.cache_fingerprint = detector.compute_cache_fingerprint();
+
+        assert_eq!(detector.cache_fingerprint, None);
+        assert_eq!(detector.detect_language_of("Alter"), Some(German));
+        assert_eq!(detector.detect_language_of("Alter"), Some(German));
+        assert_eq!(cache.statistics().misses(), 0);
+        assert_eq!(cache.statistics().len(), 0);
+    }
+
+    #[rstest]
+    fn assert_cache_statistics_are_missing_without_cache(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        assert_eq!(detector_for_english_and_german.cache_statistics(), None);
+    }
 }
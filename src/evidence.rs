@@ -0,0 +1,418 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::detector::{Evidence, LanguageDetector};
+use crate::language::Language;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
+
+/// This enum specifies how the evidence of older messages is weighted
+/// in comparison to newer ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedEvidenceWeighting")]
+pub enum EvidenceWeighting {
+    /// Only the given number of most recent messages is taken into account,
+    /// each of them with the same weight.
+    SlidingWindow(usize),
+
+    /// All messages are taken into account. Whenever a message is added, the evidence
+    /// collected so far is multiplied by the given factor which must be greater than 0.0
+    /// and less than or equal to 1.0.
+    ExponentialDecay(f64),
+}
+
+impl EvidenceWeighting {
+    fn validate(&self) -> Result<(), &'static str> {
+        match *self {
+            EvidenceWeighting::SlidingWindow(0) => {
+                Err("the sliding window must contain at least one message")
+            }
+            EvidenceWeighting::ExponentialDecay(factor) if !(factor > 0.0 && factor <= 1.0) => {
+                Err("the decay factor must be greater than 0.0 and at most 1.0")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The serialized form of [`EvidenceWeighting`] which is validated before it is accepted.
+#[derive(Deserialize)]
+enum UncheckedEvidenceWeighting {
+    SlidingWindow(usize),
+    ExponentialDecay(f64),
+}
+
+impl TryFrom<UncheckedEvidenceWeighting> for EvidenceWeighting {
+    type Error = &'static str;
+
+    fn try_from(weighting: UncheckedEvidenceWeighting) -> Result<Self, Self::Error> {
+        let weighting = match weighting {
+            UncheckedEvidenceWeighting::SlidingWindow(size) => {
+                EvidenceWeighting::SlidingWindow(size)
+            }
+            UncheckedEvidenceWeighting::ExponentialDecay(factor) => {
+                EvidenceWeighting::ExponentialDecay(factor)
+            }
+        };
+        weighting.validate()?;
+        Ok(weighting)
+    }
+}
+
+/// This struct accumulates evidence about the language of many messages, such as the messages
+/// which a single user or all participants of a chat thread have written so far.
+///
+/// Short messages like *ok* or *ja genau* are often too short to be classified on their own.
+/// For each added message, the logarithmized likelihoods which the statistical stage computes
+/// are made relative to the most likely language of the message and added up. If the
+/// rule-based engine decides on a language, that language gets a vote which outweighs all
+/// other languages of the message. Messages without any letters are ignored.
+///
+/// `LanguageEvidence` can be serialized with serde, so that it can be persisted between
+/// sessions and continued with the same detector later on. Deserialized evidence is validated
+/// in the same way as new evidence, so that corrupted data results in an error.
+///
+/// ```
+/// use lingua::{EvidenceWeighting, LanguageDetectorBuilder, LanguageEvidence};
+/// use lingua::Language::{English, German};
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+/// let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(20));
+///
+/// for message in &["ok", "ja genau", "bis morgen dann"] {
+///     evidence.add_message(&detector, *message);
+/// }
+///
+/// let (language, probability) = evidence.detect_language().unwrap();
+/// assert_eq!(language, German);
+///
+/// let json = serde_json::to_string(&evidence).unwrap();
+/// let restored: LanguageEvidence = serde_json::from_str(&json).unwrap();
+/// assert_eq!(restored.detect_language(), Some((language, probability)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedLanguageEvidence")]
+pub struct LanguageEvidence {
+    weighting: EvidenceWeighting,
+    messages: VecDeque<HashMap<Language, f64>>,
+    log_likelihoods: HashMap<Language, f64>,
+}
+
+/// The serialized form of [`LanguageEvidence`] which is validated before it is accepted.
+#[derive(Deserialize)]
+struct UncheckedLanguageEvidence {
+    weighting: EvidenceWeighting,
+    messages: VecDeque<HashMap<Language, f64>>,
+    log_likelihoods: HashMap<Language, f64>,
+}
+
+impl TryFrom<UncheckedLanguageEvidence> for LanguageEvidence {
+    type Error = &'static str;
+
+    fn try_from(evidence: UncheckedLanguageEvidence) -> Result<Self, Self::Error> {
+        let UncheckedLanguageEvidence {
+            weighting,
+            messages,
+            log_likelihoods,
+        } = evidence;
+
+        if let EvidenceWeighting::SlidingWindow(size) = weighting {
+            if messages.len() > size {
+                return Err("the sliding window contains more messages than its size");
+            }
+        }
+
+        let has_finite_log_likelihoods = messages
+            .iter()
+            .chain(Some(&log_likelihoods))
+            .flat_map(|it| it.values())
+            .all(|it| it.is_finite());
+
+        if !has_finite_log_likelihoods {
+            return Err("the log-likelihoods must be finite");
+        }
+
+        Ok(Self {
+            weighting,
+            messages,
+            log_likelihoods,
+        })
+    }
+}
+
+impl LanguageEvidence {
+    /// Creates an accumulator without any evidence which weights messages as specified.
+    ///
+    /// ⚠ Panics if the sliding window is empty or if the decay factor is not greater
+    /// than 0.0 and less than or equal to 1.0.
+    pub fn new(weighting: EvidenceWeighting) -> Self {
+        if let Err(message) = weighting.validate() {
+            panic!("{}", message);
+        }
+
+        Self {
+            weighting,
+            messages: VecDeque::new(),
+            log_likelihoods: hashmap!(),
+        }
+    }
+
+    /// Detects the language of the given message and adds the result to the evidence.
+    pub fn add_message<T: Into<String>>(&mut self, detector: &LanguageDetector, message: T) {
+        let evidence = detector.collect_evidence(message.into());
+        self.add_evidence(evidence, &detector.languages);
+    }
+
+    /// Computes the probability of each language in the evidence collected so far.
+    ///
+    /// A vector of all languages found within the evidence is returned, sorted by their
+    /// probability in descending order. Unlike the confidence values of
+    /// [`LanguageDetector`](./struct.LanguageDetector.html), the probabilities are absolute
+    /// and sum up to 1.0. If no evidence has been collected, the returned vector is empty.
+    pub fn compute_language_probabilities(&self) -> Vec<(Language, f64)> {
+        if self.log_likelihoods.is_empty() {
+            return vec![];
+        }
+
+        let highest_log_likelihood = self
+            .log_likelihoods
+            .values()
+            .cloned()
+            .fold(f64::MIN, f64::max);
+        let likelihoods = self
+            .log_likelihoods
+            .iter()
+            .map(|(language, log_likelihood)| {
+                (language, (log_likelihood - highest_log_likelihood).exp())
+            })
+            .collect_vec();
+        let total_likelihood: f64 = likelihoods.iter().map(|(_, it)| it).sum();
+
+        likelihoods
+            .into_iter()
+            .map(|(language, likelihood)| (language.clone(), likelihood / total_likelihood))
+            .sorted_by(|(first_language, first), (second_language, second)| {
+                second
+                    .partial_cmp(first)
+                    .unwrap()
+                    .then(first_language.cmp(second_language))
+            })
+            .collect_vec()
+    }
+
+    /// Returns the most likely language of the evidence collected so far together
+    /// with its probability. If no evidence has been collected, `None` is returned.
+    pub fn detect_language(&self) -> Option<(Language, f64)> {
+        self.compute_language_probabilities().into_iter().next()
+    }
+
+    fn add_evidence(&mut self, evidence: Evidence, languages: &HashSet<Language>) {
+        let log_likelihoods = match evidence.to_relative_log_likelihoods(languages) {
+            Some(log_likelihoods) => log_likelihoods,
+            None => return,
+        };
+
+        match self.weighting {
+            EvidenceWeighting::SlidingWindow(size) => {
+                self.messages.push_back(log_likelihoods);
+                if self.messages.len() > size {
+                    self.messages.pop_front();
+                }
+                self.log_likelihoods = hashmap!();
+                for message in self.messages.iter() {
+                    Self::add_log_likelihoods(&mut self.log_likelihoods, message);
+                }
+            }
+            EvidenceWeighting::ExponentialDecay(factor) => {
+                for log_likelihood in self.log_likelihoods.values_mut() {
+                    *log_likelihood *= factor;
+                }
+                Self::add_log_likelihoods(&mut self.log_likelihoods, &log_likelihoods);
+            }
+        }
+    }
+
+    fn add_log_likelihoods(
+        sums: &mut HashMap<Language, f64>,
+        log_likelihoods: &HashMap<Language, f64>,
+    ) {
+        for (language, log_likelihood) in log_likelihoods {
+            *sums.entry(language.clone()).or_insert(0.0) += log_likelihood;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::tests::detector_for_english_and_german;
+    use crate::language::Language::{English, French, German};
+    use float_cmp::approx_eq;
+    use rstest::*;
+
+    fn statistics(log_likelihoods: HashMap<Language, f64>) -> Evidence {
+        Evidence::Statistics {
+            words: vec![],
+            log_likelihoods,
+        }
+    }
+
+    #[rstest]
+    fn assert_statistical_evidence_is_accumulated(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(10));
+
+        evidence.add_message(&detector_for_english_and_german, "Alter");
+        let (language, first_probability) = evidence.detect_language().unwrap();
+        assert_eq!(language, German);
+
+        evidence.add_message(&detector_for_english_and_german, "alter");
+        let (language, second_probability) = evidence.detect_language().unwrap();
+        assert_eq!(language, German);
+        assert!(second_probability > first_probability);
+    }
+
+    #[rstest]
+    fn assert_messages_without_letters_are_ignored(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(10));
+
+        evidence.add_message(&detector_for_english_and_german, ":-) 123");
+
+        assert!(evidence.compute_language_probabilities().is_empty());
+        assert_eq!(evidence.detect_language(), None);
+    }
+
+    #[test]
+    fn assert_probabilities_are_computed_correctly() {
+        let languages = hashset!(English, German);
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(10));
+
+        evidence.add_evidence(
+            statistics(hashmap!(English => -7.0, German => -6.0)),
+            &languages,
+        );
+        evidence.add_evidence(Evidence::Rules(English), &languages);
+
+        let expected_german_probability = (-4.0_f64).exp() / (1.0 + (-4.0_f64).exp());
+        let probabilities = evidence.compute_language_probabilities();
+
+        assert_eq!(probabilities[0].0, English);
+        assert_eq!(probabilities[1].0, German);
+        assert!(approx_eq!(
+            f64,
+            probabilities[1].1,
+            expected_german_probability,
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            probabilities[0].1 + probabilities[1].1,
+            1.0,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn assert_languages_missing_from_statistics_are_least_likely() {
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(10));
+
+        evidence.add_evidence(
+            statistics(hashmap!(English => -6.0, German => -8.0)),
+            &hashset!(English, French, German),
+        );
+
+        assert_eq!(
+            evidence.log_likelihoods,
+            hashmap!(English => 0.0, French => -2.0, German => -2.0)
+        );
+    }
+
+    #[test]
+    fn assert_sliding_window_forgets_old_messages() {
+        let languages = hashset!(English, German);
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(2));
+
+        evidence.add_evidence(Evidence::Rules(English), &languages);
+        evidence.add_evidence(Evidence::Rules(German), &languages);
+        evidence.add_evidence(Evidence::Rules(German), &languages);
+
+        assert_eq!(
+            evidence.log_likelihoods,
+            hashmap!(English => -10.0, German => 0.0)
+        );
+    }
+
+    #[test]
+    fn assert_exponential_decay_weights_old_messages_less() {
+        let languages = hashset!(English, German);
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::ExponentialDecay(0.5));
+
+        evidence.add_evidence(Evidence::Rules(English), &languages);
+        evidence.add_evidence(Evidence::Rules(English), &languages);
+        evidence.add_evidence(Evidence::Rules(German), &languages);
+
+        assert_eq!(
+            evidence.log_likelihoods,
+            hashmap!(English => -5.0, German => -3.75)
+        );
+        assert_eq!(evidence.detect_language().unwrap().0, German);
+    }
+
+    #[test]
+    fn assert_evidence_can_be_serialized() {
+        let languages = hashset!(English, German);
+        let mut evidence = LanguageEvidence::new(EvidenceWeighting::SlidingWindow(3));
+
+        evidence.add_evidence(Evidence::Rules(German), &languages);
+
+        let json = serde_json::to_string(&evidence).unwrap();
+        let restored = serde_json::from_str::<LanguageEvidence>(&json).unwrap();
+
+        assert_eq!(restored, evidence);
+    }
+
+    #[rstest(
+        weighting,
+        case::empty_window(EvidenceWeighting::SlidingWindow(0)),
+        case::zero_decay(EvidenceWeighting::ExponentialDecay(0.0)),
+        case::excessive_decay(EvidenceWeighting::ExponentialDecay(1.5))
+    )]
+    #[should_panic]
+    fn assert_invalid_weighting_panics(weighting: EvidenceWeighting) {
+        LanguageEvidence::new(weighting);
+    }
+
+    #[rstest(
+        json,
+        case::empty_window(
+            r#"{"weighting":{"SlidingWindow":0},"messages":[],"log_likelihoods":{}}"#
+        ),
+        case::zero_decay(
+            r#"{"weighting":{"ExponentialDecay":0.0},"messages":[],"log_likelihoods":{}}"#
+        ),
+        case::overfull_window(
+            r#"{"weighting":{"SlidingWindow":1},"messages":[{},{}],"log_likelihoods":{}}"#
+        )
+    )]
+    fn assert_invalid_evidence_cannot_be_deserialized(json: &str) {
+        assert!(serde_json::from_str::<LanguageEvidence>(json).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// This enum specifies groups of closely related languages and macrolanguages
+/// whose members are hard to tell apart, especially in short texts.
+#[derive(Clone, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[serde(rename_all(serialize = "UPPERCASE", deserialize = "UPPERCASE"))]
+pub enum LanguageGroup {
+    /// The Malay macrolanguage, comprising Indonesian and Malay.
+    Malay,
+
+    /// Zulu and Xhosa, both belonging to the Nguni languages.
+    Nguni,
+
+    /// The Norwegian macrolanguage, comprising Bokmal and Nynorsk.
+    Norwegian,
+
+    /// Sotho and Tswana, both belonging to the Sotho-Tswana languages.
+    SothoTswana,
+
+    /// The South Slavic languages written in Latin script, namely Bosnian, Croatian and Serbian.
+    SouthSlavicLatin,
+}
+
+impl LanguageGroup {
+    pub fn all() -> HashSet<LanguageGroup> {
+        LanguageGroup::iter().collect()
+    }
+
+    /// Returns the group which the given language belongs to
+    /// or `None` if the language is not part of any group.
+    pub fn from_language(language: &Language) -> Option<LanguageGroup> {
+        LanguageGroup::iter().find(|it| it.languages().contains(language))
+    }
+
+    /// Returns the languages which belong to this group.
+    pub fn languages(&self) -> HashSet<Language> {
+        match self {
+            LanguageGroup::Malay => hashset!(Language::Indonesian, Language::Malay),
+            LanguageGroup::Nguni => hashset!(Language::Xhosa, Language::Zulu),
+            LanguageGroup::Norwegian => hashset!(Language::Bokmal, Language::Nynorsk),
+            LanguageGroup::SothoTswana => hashset!(Language::Sotho, Language::Tswana),
+            LanguageGroup::SouthSlavicLatin => {
+                hashset!(Language::Bosnian, Language::Croatian, Language::Serbian)
+            }
+        }
+    }
+}
+
+/// The result of [`LanguageDetector::detect_language_or_group_of`], being either a single
+/// language or a group of languages which could not be distinguished from each other.
+///
+/// [`LanguageDetector::detect_language_or_group_of`]: ./struct.LanguageDetector.html#method.detect_language_or_group_of
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LanguageOrGroup {
+    Language(Language),
+    Group(LanguageGroup),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+
+    #[test]
+    fn test_language_group_serializer() {
+        let serialized = serde_json::to_string(&LanguageGroup::Norwegian).unwrap();
+        assert_eq!(serialized, "\"NORWEGIAN\"");
+    }
+
+    #[test]
+    fn assert_language_groups_do_not_overlap() {
+        let languages = LanguageGroup::iter()
+            .flat_map(|it| it.languages())
+            .collect::<Vec<_>>();
+        let unique_languages = languages.iter().collect::<HashSet<_>>();
+
+        assert_eq!(languages.len(), unique_languages.len());
+    }
+
+    #[test]
+    fn assert_language_group_is_found_for_language() {
+        assert_eq!(
+            LanguageGroup::from_language(&Nynorsk),
+            Some(LanguageGroup::Norwegian)
+        );
+        assert_eq!(
+            LanguageGroup::from_language(&Indonesian),
+            Some(LanguageGroup::Malay)
+        );
+        assert_eq!(LanguageGroup::from_language(&English), None);
+    }
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// The scripts whose lowercase letters are frequently confused with each other.
+const CONFUSABLE_ALPHABETS: [Alphabet; 3] = [Alphabet::Latin, Alphabet::Cyrillic, Alphabet::Greek];
+
+/// Lists lowercase letters which look alike in Latin, Cyrillic and Greek script,
+/// in the order of `CONFUSABLE_ALPHABETS`.
+const HOMOGLYPHS: [[Option<char>; 3]; 19] = [
+    [Some('a'), Some('а'), Some('α')],
+    [Some('c'), Some('с'), None],
+    [Some('d'), Some('ԁ'), None],
+    [Some('e'), Some('е'), None],
+    [Some('h'), Some('һ'), None],
+    [Some('i'), Some('і'), Some('ι')],
+    [Some('j'), Some('ј'), None],
+    [Some('k'), Some('к'), Some('κ')],
+    [Some('l'), Some('ӏ'), None],
+    [Some('o'), Some('о'), Some('ο')],
+    [Some('p'), Some('р'), Some('ρ')],
+    [Some('q'), Some('ԛ'), None],
+    [Some('s'), Some('ѕ'), None],
+    [Some('t'), Some('т'), Some('τ')],
+    [Some('u'), None, Some('υ')],
+    [Some('v'), None, Some('ν')],
+    [Some('w'), Some('ԝ'), None],
+    [Some('x'), Some('х'), Some('χ')],
+    [Some('y'), Some('у'), Some('γ')],
+];
+
+/// Maps each script of `CONFUSABLE_ALPHABETS` to the replacements
+/// which convert look-alike letters of the other scripts into it.
+static HOMOGLYPH_TABLES: Lazy<HashMap<Alphabet, HashMap<char, char>>> = Lazy::new(|| {
+    CONFUSABLE_ALPHABETS
+        .iter()
+        .enumerate()
+        .map(|(target_index, alphabet)| {
+            let table = HOMOGLYPHS
+                .iter()
+                .filter_map(|homoglyphs| homoglyphs[target_index].map(|it| (homoglyphs, it)))
+                .flat_map(|(homoglyphs, target)| {
+                    homoglyphs
+                        .iter()
+                        .flatten()
+                        .filter(move |&&source| source != target)
+                        .map(move |&source| (source, target))
+                })
+                .collect();
+            (*alphabet, table)
+        })
+        .collect()
+});
+
+/// Replaces look-alike letters in words which mix Latin, Cyrillic and Greek script
+/// with the respective letters of the script which dominates the whole text.
+/// If several scripts are equally frequent, the first one in the order of `Alphabet`
+/// dominates. Words written in a single script are left untouched.
+///
+/// Returns `None` if no letter has been replaced.
+pub(crate) fn normalize_homoglyphs(text: &str) -> Option<String> {
+    let mut alphabet_counts = HashMap::<Alphabet, u32>::new();
+
+    for character in text.chars() {
+        if let Some(alphabet) = find_confusable_alphabet(character) {
+            *alphabet_counts.entry(alphabet).or_default() += 1;
+        }
+    }
+
+    let dominant_alphabet = alphabet_counts
+        .into_iter()
+        .sorted_by(
+            |(first_alphabet, first_count), (second_alphabet, second_count)| {
+                second_count
+                    .cmp(first_count)
+                    .then(first_alphabet.cmp(second_alphabet))
+            },
+        )
+        .next()
+        .map(|(alphabet, _)| alphabet)?;
+    let table = HOMOGLYPH_TABLES.get(&dominant_alphabet).unwrap();
+    let mut is_normalized = false;
+
+    let normalized_text = text
+        .split(' ')
+        .map(|word| {
+            let is_mixed_script = word
+                .chars()
+                .filter_map(find_confusable_alphabet)
+                .unique()
+                .count()
+                > 1;
+
+            if !is_mixed_script {
+                return word.to_string();
+            }
+
+            word.chars()
+                .map(|character| match table.get(&character) {
+                    Some(&replacement) => {
+                        is_normalized = true;
+                        replacement
+                    }
+                    None => character,
+                })
+                .collect()
+        })
+        .join(" ");
+
+    if is_normalized {
+        Some(normalized_text)
+    } else {
+        None
+    }
+}
+
+fn find_confusable_alphabet(character: char) -> Option<Alphabet> {
+    let mut buffer = [0; 4];
+    let char_str = character.encode_utf8(&mut buffer);
+
+    CONFUSABLE_ALPHABETS
+        .iter()
+        .find(|alphabet| alphabet.matches(char_str))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_cyrillic_homoglyphs_in_latin_text_are_normalized() {
+        assert_eq!(
+            normalize_homoglyphs("log in to your pаypаl account"),
+            Some("log in to your paypal account".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_latin_homoglyphs_in_cyrillic_text_are_normalized() {
+        assert_eq!(
+            normalize_homoglyphs("вoйти в систему"),
+            Some("войти в систему".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_equally_frequent_scripts_are_resolved_deterministically() {
+        for _ in 0..10 {
+            assert_eq!(normalize_homoglyphs("pа"), Some("ра".to_string()));
+        }
+    }
+
+    #[test]
+    fn assert_words_in_single_script_are_not_normalized() {
+        assert_eq!(normalize_homoglyphs("the word москва is russian"), None);
+        assert_eq!(normalize_homoglyphs("paypal"), None);
+        assert_eq!(normalize_homoglyphs(""), None);
+    }
+}
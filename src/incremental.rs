@@ -0,0 +1,357 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::constant::{LETTER, NO_LETTER};
+use crate::detector::LanguageDetector;
+use crate::language::Language;
+use crate::model::{fold_diacritics, TestDataLanguageModel};
+use crate::ngram::Ngram;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+/// The number of characters preceding the appended part of the cleaned up text
+/// which new ngrams can start with.
+const MAXIMUM_NGRAM_OVERLAP: usize = 4;
+
+/// This struct detects the language of text which grows piece by piece,
+/// such as text which is being typed into an editor.
+///
+/// The whole text is cleaned up again after each change, so that text preprocessors and
+/// homoglyph normalization see words which have been typed piece by piece. As long as the
+/// cleaned up text only grows at its end, only the ngrams which are added by the appended
+/// text are looked up in the language models. Otherwise, for instance if a URL has been
+/// completed and removed, or if the proper nouns to down-weight or the choice of folded
+/// language models have changed, all ngrams of the text are looked up again.
+///
+/// The ranking of languages is the same as the one computed by
+/// [`LanguageDetector::compute_language_confidence_values`] for the whole text,
+/// except that romanized language models, transliteration, sampling and adaptive
+/// pruning are not applied.
+///
+/// [`LanguageDetector::compute_language_confidence_values`]: ./struct.LanguageDetector.html#method.compute_language_confidence_values
+///
+/// ```
+/// use lingua::{IncrementalDetector, LanguageDetectorBuilder};
+/// use lingua::Language::{English, French};
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, French]).build();
+/// let mut incremental_detector = IncrementalDetector::new(&detector);
+///
+/// incremental_detector.append("languages ");
+/// incremental_detector.append("are awesome");
+///
+/// assert_eq!(incremental_detector.text(), "languages are awesome");
+/// assert_eq!(incremental_detector.detect_language(), Some(English));
+/// ```
+pub struct IncrementalDetector<'a> {
+    detector: &'a LanguageDetector,
+    text: String,
+    cleaned_up_text: String,
+    use_folded_models: bool,
+    down_weighted_ngrams: Vec<HashSet<Ngram>>,
+    seen_ngrams: Vec<HashSet<Ngram>>,
+    summed_up_probabilities: Vec<HashMap<Language, f64>>,
+    unigram_counts: HashMap<Language, u32>,
+}
+
+impl<'a> IncrementalDetector<'a> {
+    /// Creates a new session without any text for the given detector.
+    pub fn new(detector: &'a LanguageDetector) -> Self {
+        Self {
+            detector,
+            text: String::new(),
+            cleaned_up_text: String::new(),
+            use_folded_models: false,
+            down_weighted_ngrams: vec![hashset!(); 5],
+            seen_ngrams: vec![hashset!(); 5],
+            summed_up_probabilities: vec![hashmap!(); 5],
+            unigram_counts: hashmap!(),
+        }
+    }
+
+    /// Returns the text which has been appended so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Appends the given text and updates the ngram probabilities of all languages
+    /// with the ngrams which have been added by it.
+    pub fn append(&mut self, text: &str) {
+        self.text.push_str(text);
+
+        let down_weighted_words = self.detector.find_down_weighted_words(&self.text);
+        let cleaned_up_text = self.detector.clean_up_input_text(self.text.clone());
+        let cleaned_up_text = self
+            .detector
+            .normalize_mixed_script_words(&cleaned_up_text)
+            .unwrap_or(cleaned_up_text);
+        let words = self.detector.split_text_into_words(&cleaned_up_text);
+        let down_weighted_positions = self
+            .detector
+            .find_down_weighted_positions(&words, &down_weighted_words);
+        let down_weighted_ngrams = (1..6)
+            .map(|ngram_length| {
+                self.detector.find_down_weighted_ngrams(
+                    &cleaned_up_text,
+                    &down_weighted_positions,
+                    ngram_length,
+                )
+            })
+            .collect_vec();
+        let use_folded_models = self.detector.use_folded_language_models
+            && fold_diacritics(&cleaned_up_text) == cleaned_up_text;
+
+        let is_extension = cleaned_up_text.starts_with(&self.cleaned_up_text)
+            && down_weighted_ngrams == self.down_weighted_ngrams
+            && use_folded_models == self.use_folded_models;
+
+        if !is_extension {
+            self.seen_ngrams = vec![hashset!(); 5];
+            self.summed_up_probabilities = vec![hashmap!(); 5];
+            self.unigram_counts = hashmap!();
+            self.cleaned_up_text.clear();
+        }
+
+        let previous_length = self.cleaned_up_text.chars().count();
+        let overlap_length = previous_length.min(MAXIMUM_NGRAM_OVERLAP);
+        let chars = cleaned_up_text
+            .chars()
+            .skip(previous_length - overlap_length)
+            .collect_vec();
+
+        self.cleaned_up_text = cleaned_up_text;
+        self.down_weighted_ngrams = down_weighted_ngrams;
+        self.use_folded_models = use_folded_models;
+
+        for ngram_length in 1..6 {
+            self.add_ngrams(&chars, overlap_length, ngram_length);
+        }
+    }
+
+    /// Computes confidence values for each language considered possible for the text
+    /// appended so far, in the same way as
+    /// [`LanguageDetector::compute_language_confidence_values`](./struct.LanguageDetector.html#method.compute_language_confidence_values).
+    pub fn compute_language_confidence_values(&self) -> Vec<(Language, f64)> {
+        let text = &self.cleaned_up_text;
+
+        if text.is_empty() || NO_LETTER.is_match(text) {
+            return vec![];
+        }
+
+        let words = self.detector.split_text_into_words(text);
+
+        if let Some(language) = self.detector.detect_language_with_rules(&words) {
+            return vec![(language, 1.0)];
+        }
+
+        let mut filtered_languages = self.detector.filter_languages_by_rules(&words);
+
+        if filtered_languages.len() == 1 {
+            let filtered_language = filtered_languages.into_iter().next().unwrap();
+            return vec![(filtered_language, 1.0)];
+        }
+
+        let char_count = text.chars().count();
+        let mut all_probabilities = vec![];
+
+        for (i, summed_up_probabilities) in self.summed_up_probabilities.iter().enumerate() {
+            if char_count <= i {
+                continue;
+            }
+
+            let probabilities = summed_up_probabilities
+                .iter()
+                .filter(|(language, &sum)| sum < 0.0 && filtered_languages.contains(language))
+                .map(|(language, &sum)| (language.clone(), sum))
+                .collect::<HashMap<_, _>>();
+
+            if !probabilities.is_empty() {
+                filtered_languages.retain(|it| probabilities.contains_key(it));
+            }
+
+            all_probabilities.push(probabilities);
+        }
+
+        let input_texts = vec![(text.clone(), filtered_languages.clone())];
+
+        let summed_up_probabilities = self.detector.combine_probabilities(
+            &input_texts,
+            all_probabilities,
+            self.unigram_counts.clone(),
+            filtered_languages,
+        );
+
+        self.detector
+            .rank_languages(&words, summed_up_probabilities)
+    }
+
+    /// Detects the language of the text appended so far.
+    /// If the language cannot be reliably detected, `None` is returned.
+    pub fn detect_language(&self) -> Option<Language> {
+        self.detector
+            .select_most_likely_language(&self.compute_language_confidence_values())
+    }
+
+    /// Looks up the ngrams of the given length which end after the given number of
+    /// overlapping characters and adds their probabilities to the sums of all languages.
+    fn add_ngrams(&mut self, chars: &[char], overlap_length: usize, ngram_length: usize) {
+        let mut ngram_counts = HashMap::<Ngram, u32>::new();
+
+        for end in (overlap_length + 1).max(ngram_length)..=chars.len() {
+            let slice = chars[end - ngram_length..end].iter().collect::<String>();
+            if LETTER.is_match(&slice) {
+                *ngram_counts.entry(Ngram::new(&slice)).or_insert(0) += 1;
+            }
+        }
+
+        let seen_ngrams = &mut self.seen_ngrams[ngram_length - 1];
+        let unseen_ngrams = ngram_counts
+            .keys()
+            .filter(|ngram| !seen_ngrams.contains(ngram))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let model = if self.detector.weight_ngrams_by_count {
+            TestDataLanguageModel {
+                ngrams: ngram_counts.keys().cloned().collect(),
+                ngram_counts,
+            }
+        } else {
+            TestDataLanguageModel {
+                ngrams: unseen_ngrams.clone(),
+                ngram_counts: unseen_ngrams.iter().map(|it| (it.clone(), 1)).collect(),
+            }
+        };
+
+        for language in self.detector.languages.iter() {
+            let sum = self.detector.compute_sum_of_ngram_probabilities(
+                language,
+                &model,
+                self.use_folded_models,
+                &self.down_weighted_ngrams[ngram_length - 1],
+            );
+            *self.summed_up_probabilities[ngram_length - 1]
+                .entry(language.clone())
+                .or_insert(0.0) += sum;
+
+            if ngram_length == 1 {
+                for unigram in unseen_ngrams.iter() {
+                    if self.detector.look_up_ngram_probability(
+                        language,
+                        unigram,
+                        self.use_folded_models,
+                    ) > 0.0
+                    {
+                        *self.unigram_counts.entry(language.clone()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        seen_ngrams.extend(unseen_ngrams);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::tests::detector_for_english_and_german;
+    use crate::preprocessor::default_text_preprocessors;
+    use rstest::*;
+
+    #[rstest(
+        pieces,
+        use_default_text_preprocessors,
+        use_folded_language_models,
+        down_weight_proper_nouns,
+        case(vec!["Alter"], false, false, false),
+        case(vec!["Al", "ter"], false, false, false),
+        case(vec!["Alt", "er ", "Al", "ter"], false, false, false),
+        case(vec!["Alter", " ", "alter!"], false, false, false),
+        case(vec!["Alter @al", "ter alter"], true, false, false),
+        case(vec!["Alter al", "ter://alter", " alter"], true, false, false),
+        case(vec!["Al", "ter al", "ter"], false, true, false),
+        case(vec!["ter Al", "ter"], false, false, true),
+        case(vec!["ter", " Alter", " ter"], false, false, true)
+    )]
+    fn assert_incremental_detection_equals_detection_of_whole_text(
+        detector_for_english_and_german: LanguageDetector,
+        pieces: Vec<&str>,
+        use_default_text_preprocessors: bool,
+        use_folded_language_models: bool,
+        down_weight_proper_nouns: bool,
+    ) {
+        let mut detector = detector_for_english_and_german;
+
+        if use_default_text_preprocessors {
+            detector.text_preprocessors = default_text_preprocessors();
+        }
+        detector.use_folded_language_models = use_folded_language_models;
+        detector.down_weight_proper_nouns = down_weight_proper_nouns;
+
+        let mut incremental_detector = IncrementalDetector::new(&detector);
+
+        for piece in pieces.iter() {
+            incremental_detector.append(piece);
+        }
+
+        let text = pieces.concat();
+
+        assert_eq!(incremental_detector.text(), text);
+
+        let incremental_values = incremental_detector.compute_language_confidence_values();
+        let expected_values = detector.compute_language_confidence_values(text);
+
+        assert_eq!(incremental_values.len(), expected_values.len());
+
+        for ((language, value), (expected_language, expected_value)) in
+            incremental_values.iter().zip(expected_values.iter())
+        {
+            assert_eq!(language, expected_language);
+            assert!((value - expected_value).abs() < 1e-9);
+        }
+    }
+
+    #[rstest]
+    fn assert_only_new_ngrams_are_added(detector_for_english_and_german: LanguageDetector) {
+        let mut incremental_detector = IncrementalDetector::new(&detector_for_english_and_german);
+
+        incremental_detector.append("al");
+        assert_eq!(
+            incremental_detector.seen_ngrams[1],
+            hashset!(Ngram::new("al"))
+        );
+
+        incremental_detector.append("te");
+        assert_eq!(
+            incremental_detector.seen_ngrams[1],
+            hashset!(Ngram::new("al"), Ngram::new("lt"), Ngram::new("te"))
+        );
+        assert_eq!(
+            incremental_detector.seen_ngrams[3],
+            hashset!(Ngram::new("alte"))
+        );
+    }
+
+    #[rstest]
+    fn assert_empty_session_detects_no_language(detector_for_english_and_german: LanguageDetector) {
+        let incremental_detector = IncrementalDetector::new(&detector_for_english_and_german);
+        assert!(incremental_detector
+            .compute_language_confidence_values()
+            .is_empty());
+        assert_eq!(incremental_detector.detect_language(), None);
+    }
+}
@@ -24,6 +24,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Afrikaans`](./enum.Language.html#variant.Afrikaans)
     AF,
 
+    /// The ISO 639-1 code for [`Amharic`](./enum.Language.html#variant.Amharic)
+    AM,
+
     /// The ISO 639-1 code for [`Arabic`](./enum.Language.html#variant.Arabic)
     AR,
 
@@ -39,6 +42,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Bengali`](./enum.Language.html#variant.Bengali)
     BN,
 
+    /// The ISO 639-1 code for [`Tibetan`](./enum.Language.html#variant.Tibetan)
+    BO,
+
     /// The ISO 639-1 code for [`Bosnian`](./enum.Language.html#variant.Bosnian)
     BS,
 
@@ -57,6 +63,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`German`](./enum.Language.html#variant.German)
     DE,
 
+    /// The ISO 639-1 code for [`Dhivehi`](./enum.Language.html#variant.Dhivehi)
+    DV,
+
     /// The ISO 639-1 code for [`Greek`](./enum.Language.html#variant.Greek)
     EL,
 
@@ -123,6 +132,12 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Kazakh`](./enum.Language.html#variant.Kazakh)
     KK,
 
+    /// The ISO 639-1 code for [`Khmer`](./enum.Language.html#variant.Khmer)
+    KM,
+
+    /// The ISO 639-1 code for [`Kannada`](./enum.Language.html#variant.Kannada)
+    KN,
+
     /// The ISO 639-1 code for [`Korean`](./enum.Language.html#variant.Korean)
     KO,
 
@@ -132,6 +147,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Ganda`](./enum.Language.html#variant.Ganda)
     LG,
 
+    /// The ISO 639-1 code for [`Lao`](./enum.Language.html#variant.Lao)
+    LO,
+
     /// The ISO 639-1 code for [`Lithuanian`](./enum.Language.html#variant.Lithuanian)
     LT,
 
@@ -144,6 +162,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Macedonian`](./enum.Language.html#variant.Macedonian)
     MK,
 
+    /// The ISO 639-1 code for [`Malayalam`](./enum.Language.html#variant.Malayalam)
+    ML,
+
     /// The ISO 639-1 code for [`Mongolian`](./enum.Language.html#variant.Mongolian)
     MN,
 
@@ -153,6 +174,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Malay`](./enum.Language.html#variant.Malay)
     MS,
 
+    /// The ISO 639-1 code for [`Burmese`](./enum.Language.html#variant.Burmese)
+    MY,
+
     /// The ISO 639-1 code for [`Norwegian Bokmal`](./enum.Language.html#variant.Bokmal)
     NB,
 
@@ -162,6 +186,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Norwegian Nynorsk`](./enum.Language.html#variant.Nynorsk)
     NN,
 
+    /// The ISO 639-1 code for [`Odia`](./enum.Language.html#variant.Odia)
+    OR,
+
     /// The ISO 639-1 code for [`Punjabi`](./enum.Language.html#variant.Punjabi)
     PA,
 
@@ -177,6 +204,9 @@ pub enum IsoCode639_1 {
     /// The ISO 639-1 code for [`Russian`](./enum.Language.html#variant.Russian)
     RU,
 
+    /// The ISO 639-1 code for [`Sinhala`](./enum.Language.html#variant.Sinhala)
+    SI,
+
     /// The ISO 639-1 code for [`Slovak`](./enum.Language.html#variant.Slovak)
     SK,
 
@@ -255,6 +285,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Afrikaans`](./enum.Language.html#variant.Afrikaans)
     AFR,
 
+    /// The ISO 639-3 code for [`Amharic`](./enum.Language.html#variant.Amharic)
+    AMH,
+
     /// The ISO 639-3 code for [`Arabic`](./enum.Language.html#variant.Arabic)
     ARA,
 
@@ -267,6 +300,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Bengali`](./enum.Language.html#variant.Bengali)
     BEN,
 
+    /// The ISO 639-3 code for [`Tibetan`](./enum.Language.html#variant.Tibetan)
+    BOD,
+
     /// The ISO 639-3 code for [`Bosnian`](./enum.Language.html#variant.Bosnian)
     BOS,
 
@@ -288,6 +324,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`German`](./enum.Language.html#variant.German)
     DEU,
 
+    /// The ISO 639-3 code for [`Dhivehi`](./enum.Language.html#variant.Dhivehi)
+    DIV,
+
     /// The ISO 639-3 code for [`Greek`](./enum.Language.html#variant.Greek)
     ELL,
 
@@ -345,15 +384,24 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Japanese`](./enum.Language.html#variant.Japanese)
     JPN,
 
+    /// The ISO 639-3 code for [`Kannada`](./enum.Language.html#variant.Kannada)
+    KAN,
+
     /// The ISO 639-3 code for [`Georgian`](./enum.Language.html#variant.Georgian)
     KAT,
 
     /// The ISO 639-3 code for [`Kazakh`](./enum.Language.html#variant.Kazakh)
     KAZ,
 
+    /// The ISO 639-3 code for [`Khmer`](./enum.Language.html#variant.Khmer)
+    KHM,
+
     /// The ISO 639-3 code for [`Korean`](./enum.Language.html#variant.Korean)
     KOR,
 
+    /// The ISO 639-3 code for [`Lao`](./enum.Language.html#variant.Lao)
+    LAO,
+
     /// The ISO 639-3 code for [`Latin`](./enum.Language.html#variant.Latin)
     LAT,
 
@@ -366,6 +414,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Ganda`](./enum.Language.html#variant.Ganda)
     LUG,
 
+    /// The ISO 639-3 code for [`Malayalam`](./enum.Language.html#variant.Malayalam)
+    MAL,
+
     /// The ISO 639-3 code for [`Marathi`](./enum.Language.html#variant.Marathi)
     MAR,
 
@@ -381,6 +432,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Malay`](./enum.Language.html#variant.Malay)
     MSA,
 
+    /// The ISO 639-3 code for [`Burmese`](./enum.Language.html#variant.Burmese)
+    MYA,
+
     /// The ISO 639-3 code for [`Dutch`](./enum.Language.html#variant.Dutch)
     NLD,
 
@@ -390,6 +444,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Norwegian Bokmal`](./enum.Language.html#variant.Bokmal)
     NOB,
 
+    /// The ISO 639-3 code for [`Odia`](./enum.Language.html#variant.Odia)
+    ORI,
+
     /// The ISO 639-3 code for [`Punjabi`](./enum.Language.html#variant.Punjabi)
     PAN,
 
@@ -405,6 +462,9 @@ pub enum IsoCode639_3 {
     /// The ISO 639-3 code for [`Russian`](./enum.Language.html#variant.Russian)
     RUS,
 
+    /// The ISO 639-3 code for [`Sinhala`](./enum.Language.html#variant.Sinhala)
+    SIN,
+
     /// The ISO 639-3 code for [`Slovak`](./enum.Language.html#variant.Slovak)
     SLK,
 
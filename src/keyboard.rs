@@ -0,0 +1,142 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+use crate::language::Language::*;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+type KeyboardLayout = HashMap<char, &'static str>;
+
+/// Maps languages to the characters which their standard keyboard layouts
+/// produce for the keys of the US QWERTY layout.
+static KEYBOARD_LAYOUTS: Lazy<HashMap<Language, KeyboardLayout>> = Lazy::new(|| {
+    hashmap!(
+        Arabic => hashmap!(
+            'q' => "ض", 'w' => "ص", 'e' => "ث", 'r' => "ق", 't' => "ف", 'y' => "غ",
+            'u' => "ع", 'i' => "ه", 'o' => "خ", 'p' => "ح", '[' => "ج", ']' => "د",
+            'a' => "ش", 's' => "س", 'd' => "ي", 'f' => "ب", 'g' => "ل", 'h' => "ا",
+            'j' => "ت", 'k' => "ن", 'l' => "م", ';' => "ك", '\'' => "ط", 'z' => "ئ",
+            'x' => "ء", 'c' => "ؤ", 'v' => "ر", 'b' => "لا", 'n' => "ى", 'm' => "ة",
+            ',' => "و", '.' => "ز", '/' => "ظ"
+        ),
+        Bulgarian => hashmap!(
+            'q' => "ы", 'w' => "у", 'e' => "е", 'r' => "и", 't' => "ш", 'y' => "щ",
+            'u' => "к", 'i' => "с", 'o' => "д", 'p' => "з", '[' => "ц", 'a' => "ь",
+            's' => "я", 'd' => "а", 'f' => "о", 'g' => "ж", 'h' => "г", 'j' => "т",
+            'k' => "н", 'l' => "в", ';' => "м", '\'' => "ч", 'z' => "ю", 'x' => "й",
+            'c' => "ъ", 'v' => "э", 'b' => "ф", 'n' => "х", 'm' => "п", ',' => "р",
+            '.' => "л", '/' => "б"
+        ),
+        Greek => hashmap!(
+            'q' => ";", 'w' => "ς", 'e' => "ε", 'r' => "ρ", 't' => "τ", 'y' => "υ",
+            'u' => "θ", 'i' => "ι", 'o' => "ο", 'p' => "π", 'a' => "α", 's' => "σ",
+            'd' => "δ", 'f' => "φ", 'g' => "γ", 'h' => "η", 'j' => "ξ", 'k' => "κ",
+            'l' => "λ", 'z' => "ζ", 'x' => "χ", 'c' => "ψ", 'v' => "ω", 'b' => "β",
+            'n' => "ν", 'm' => "μ"
+        ),
+        Hebrew => hashmap!(
+            'q' => "/", 'w' => "'", 'e' => "ק", 'r' => "ר", 't' => "א", 'y' => "ט",
+            'u' => "ו", 'i' => "ן", 'o' => "ם", 'p' => "פ", 'a' => "ש", 's' => "ד",
+            'd' => "ג", 'f' => "כ", 'g' => "ע", 'h' => "י", 'j' => "ח", 'k' => "ל",
+            'l' => "ך", ';' => "ף", '\'' => ",", 'z' => "ז", 'x' => "ס", 'c' => "ב",
+            'v' => "ה", 'b' => "נ", 'n' => "מ", 'm' => "צ", ',' => "ת", '.' => "ץ",
+            '/' => "."
+        ),
+        Russian => hashmap!(
+            '`' => "ё", 'q' => "й", 'w' => "ц", 'e' => "у", 'r' => "к", 't' => "е",
+            'y' => "н", 'u' => "г", 'i' => "ш", 'o' => "щ", 'p' => "з", '[' => "х",
+            ']' => "ъ", 'a' => "ф", 's' => "ы", 'd' => "в", 'f' => "а", 'g' => "п",
+            'h' => "р", 'j' => "о", 'k' => "л", 'l' => "д", ';' => "ж", '\'' => "э",
+            'z' => "я", 'x' => "ч", 'c' => "с", 'v' => "м", 'b' => "и", 'n' => "т",
+            'm' => "ь", ',' => "б", '.' => "ю"
+        ),
+        Ukrainian => hashmap!(
+            '`' => "'", 'q' => "й", 'w' => "ц", 'e' => "у", 'r' => "к", 't' => "е",
+            'y' => "н", 'u' => "г", 'i' => "ш", 'o' => "щ", 'p' => "з", '[' => "х",
+            ']' => "ї", 'a' => "ф", 's' => "і", 'd' => "в", 'f' => "а", 'g' => "п",
+            'h' => "р", 'j' => "о", 'k' => "л", 'l' => "д", ';' => "ж", '\'' => "є",
+            'z' => "я", 'x' => "ч", 'c' => "с", 'v' => "м", 'b' => "и", 'n' => "т",
+            'm' => "ь", ',' => "б", '.' => "ю"
+        )
+    )
+});
+
+/// Returns `true` if a standard keyboard layout is known for the given language.
+pub(crate) fn has_keyboard_layout(language: &Language) -> bool {
+    KEYBOARD_LAYOUTS.contains_key(language)
+}
+
+/// Converts the given text, assumed to have been typed with the US QWERTY layout,
+/// into the text that the same keys produce with the keyboard layout of the given language.
+/// Uppercase letters are converted into the uppercase form of the produced characters.
+///
+/// Returns `None` if no keyboard layout is known for the language.
+pub(crate) fn remap_keyboard_layout(text: &str, language: &Language) -> Option<String> {
+    let layout = KEYBOARD_LAYOUTS.get(language)?;
+    let mut result = String::with_capacity(text.len() * 2);
+
+    for character in text.chars() {
+        let key = character.to_ascii_lowercase();
+        match layout.get(&key) {
+            Some(target) if key != character => result.push_str(&target.to_uppercase()),
+            Some(target) => result.push_str(target),
+            None => result.push(character),
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_text_is_remapped_to_cyrillic_keyboard_layouts() {
+        assert_eq!(
+            remap_keyboard_layout("Ghbdtn rfr ltkf?", &Russian),
+            Some("Привет как дела?".to_string())
+        );
+        assert_eq!(
+            remap_keyboard_layout("ljhjuf", &Ukrainian),
+            Some("дорога".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_text_is_remapped_to_greek_keyboard_layout() {
+        assert_eq!(
+            remap_keyboard_layout("kalhm'era", &Greek),
+            Some("καλημ'ερα".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_text_is_remapped_to_hebrew_keyboard_layout() {
+        assert_eq!(
+            remap_keyboard_layout("akuo", &Hebrew),
+            Some("שלום".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_text_is_not_remapped_without_keyboard_layout() {
+        assert!(has_keyboard_layout(&Russian));
+        assert!(!has_keyboard_layout(&English));
+        assert_eq!(remap_keyboard_layout("house", &English), None);
+    }
+}
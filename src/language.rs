@@ -21,12 +21,13 @@ use std::collections::HashSet;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-/// This enum specifies the so far 75 supported languages which can be detected by *Lingua*.
+/// This enum specifies the so far 85 supported languages which can be detected by *Lingua*.
 #[derive(Clone, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[serde(rename_all(serialize = "UPPERCASE", deserialize = "UPPERCASE"))]
 pub enum Language {
     Afrikaans,
     Albanian,
+    Amharic,
     Arabic,
     Armenian,
     Azerbaijani,
@@ -36,11 +37,13 @@ pub enum Language {
     Bokmal,
     Bosnian,
     Bulgarian,
+    Burmese,
     Catalan,
     Chinese,
     Croatian,
     Czech,
     Danish,
+    Dhivehi,
     Dutch,
     English,
     Esperanto,
@@ -60,17 +63,22 @@ pub enum Language {
     Irish,
     Italian,
     Japanese,
+    Kannada,
     Kazakh,
+    Khmer,
     Korean,
+    Lao,
     Latin,
     Latvian,
     Lithuanian,
     Macedonian,
     Malay,
+    Malayalam,
     Maori,
     Marathi,
     Mongolian,
     Nynorsk,
+    Odia,
     Persian,
     Polish,
     Portuguese,
@@ -79,6 +87,7 @@ pub enum Language {
     Russian,
     Serbian,
     Shona,
+    Sinhala,
     Slovak,
     Slovene,
     Somali,
@@ -90,6 +99,7 @@ pub enum Language {
     Tamil,
     Telugu,
     Thai,
+    Tibetan,
     Tsonga,
     Tswana,
     Turkish,
@@ -153,6 +163,7 @@ impl Language {
         match self {
             Language::Afrikaans => IsoCode639_1::AF,
             Language::Albanian => IsoCode639_1::SQ,
+            Language::Amharic => IsoCode639_1::AM,
             Language::Arabic => IsoCode639_1::AR,
             Language::Armenian => IsoCode639_1::HY,
             Language::Azerbaijani => IsoCode639_1::AZ,
@@ -162,11 +173,13 @@ impl Language {
             Language::Bokmal => IsoCode639_1::NB,
             Language::Bosnian => IsoCode639_1::BS,
             Language::Bulgarian => IsoCode639_1::BG,
+            Language::Burmese => IsoCode639_1::MY,
             Language::Catalan => IsoCode639_1::CA,
             Language::Chinese => IsoCode639_1::ZH,
             Language::Croatian => IsoCode639_1::HR,
             Language::Czech => IsoCode639_1::CS,
             Language::Danish => IsoCode639_1::DA,
+            Language::Dhivehi => IsoCode639_1::DV,
             Language::Dutch => IsoCode639_1::NL,
             Language::English => IsoCode639_1::EN,
             Language::Esperanto => IsoCode639_1::EO,
@@ -186,17 +199,22 @@ impl Language {
             Language::Irish => IsoCode639_1::GA,
             Language::Italian => IsoCode639_1::IT,
             Language::Japanese => IsoCode639_1::JA,
+            Language::Kannada => IsoCode639_1::KN,
             Language::Kazakh => IsoCode639_1::KK,
+            Language::Khmer => IsoCode639_1::KM,
             Language::Korean => IsoCode639_1::KO,
+            Language::Lao => IsoCode639_1::LO,
             Language::Latin => IsoCode639_1::LA,
             Language::Latvian => IsoCode639_1::LV,
             Language::Lithuanian => IsoCode639_1::LT,
             Language::Macedonian => IsoCode639_1::MK,
             Language::Malay => IsoCode639_1::MS,
+            Language::Malayalam => IsoCode639_1::ML,
             Language::Maori => IsoCode639_1::MI,
             Language::Marathi => IsoCode639_1::MR,
             Language::Mongolian => IsoCode639_1::MN,
             Language::Nynorsk => IsoCode639_1::NN,
+            Language::Odia => IsoCode639_1::OR,
             Language::Persian => IsoCode639_1::FA,
             Language::Polish => IsoCode639_1::PL,
             Language::Portuguese => IsoCode639_1::PT,
@@ -205,6 +223,7 @@ impl Language {
             Language::Russian => IsoCode639_1::RU,
             Language::Serbian => IsoCode639_1::SR,
             Language::Shona => IsoCode639_1::SN,
+            Language::Sinhala => IsoCode639_1::SI,
             Language::Slovak => IsoCode639_1::SK,
             Language::Slovene => IsoCode639_1::SL,
             Language::Somali => IsoCode639_1::SO,
@@ -216,6 +235,7 @@ impl Language {
             Language::Tamil => IsoCode639_1::TA,
             Language::Telugu => IsoCode639_1::TE,
             Language::Thai => IsoCode639_1::TH,
+            Language::Tibetan => IsoCode639_1::BO,
             Language::Tsonga => IsoCode639_1::TS,
             Language::Tswana => IsoCode639_1::TN,
             Language::Turkish => IsoCode639_1::TR,
@@ -233,6 +253,7 @@ impl Language {
         match self {
             Language::Afrikaans => IsoCode639_3::AFR,
             Language::Albanian => IsoCode639_3::SQI,
+            Language::Amharic => IsoCode639_3::AMH,
             Language::Arabic => IsoCode639_3::ARA,
             Language::Armenian => IsoCode639_3::HYE,
             Language::Azerbaijani => IsoCode639_3::AZE,
@@ -242,11 +263,13 @@ impl Language {
             Language::Bokmal => IsoCode639_3::NOB,
             Language::Bosnian => IsoCode639_3::BOS,
             Language::Bulgarian => IsoCode639_3::BUL,
+            Language::Burmese => IsoCode639_3::MYA,
             Language::Catalan => IsoCode639_3::CAT,
             Language::Chinese => IsoCode639_3::ZHO,
             Language::Croatian => IsoCode639_3::HRV,
             Language::Czech => IsoCode639_3::CES,
             Language::Danish => IsoCode639_3::DAN,
+            Language::Dhivehi => IsoCode639_3::DIV,
             Language::Dutch => IsoCode639_3::NLD,
             Language::English => IsoCode639_3::ENG,
             Language::Esperanto => IsoCode639_3::EPO,
@@ -266,17 +289,22 @@ impl Language {
             Language::Irish => IsoCode639_3::GLE,
             Language::Italian => IsoCode639_3::ITA,
             Language::Japanese => IsoCode639_3::JPN,
+            Language::Kannada => IsoCode639_3::KAN,
             Language::Kazakh => IsoCode639_3::KAZ,
+            Language::Khmer => IsoCode639_3::KHM,
             Language::Korean => IsoCode639_3::KOR,
+            Language::Lao => IsoCode639_3::LAO,
             Language::Latin => IsoCode639_3::LAT,
             Language::Latvian => IsoCode639_3::LAV,
             Language::Lithuanian => IsoCode639_3::LIT,
             Language::Macedonian => IsoCode639_3::MKD,
             Language::Malay => IsoCode639_3::MSA,
+            Language::Malayalam => IsoCode639_3::MAL,
             Language::Maori => IsoCode639_3::MRI,
             Language::Marathi => IsoCode639_3::MAR,
             Language::Mongolian => IsoCode639_3::MON,
             Language::Nynorsk => IsoCode639_3::NNO,
+            Language::Odia => IsoCode639_3::ORI,
             Language::Persian => IsoCode639_3::FAS,
             Language::Polish => IsoCode639_3::POL,
             Language::Portuguese => IsoCode639_3::POR,
@@ -285,6 +313,7 @@ impl Language {
             Language::Russian => IsoCode639_3::RUS,
             Language::Serbian => IsoCode639_3::SRP,
             Language::Shona => IsoCode639_3::SNA,
+            Language::Sinhala => IsoCode639_3::SIN,
             Language::Slovak => IsoCode639_3::SLK,
             Language::Slovene => IsoCode639_3::SLV,
             Language::Somali => IsoCode639_3::SOM,
@@ -296,6 +325,7 @@ impl Language {
             Language::Tamil => IsoCode639_3::TAM,
             Language::Telugu => IsoCode639_3::TEL,
             Language::Thai => IsoCode639_3::THA,
+            Language::Tibetan => IsoCode639_3::BOD,
             Language::Tsonga => IsoCode639_3::TSO,
             Language::Tswana => IsoCode639_3::TSN,
             Language::Turkish => IsoCode639_3::TUR,
@@ -309,11 +339,11 @@ impl Language {
         }
     }
 
-    pub(crate) fn alphabets(&self) -> HashSet<Alphabet> {
+    /// Returns the scripts that this language is written in.
+    pub fn alphabets(&self) -> HashSet<Alphabet> {
         match self {
             Language::Afrikaans
             | Language::Albanian
-            | Language::Azerbaijani
             | Language::Basque
             | Language::Bokmal
             | Language::Bosnian
@@ -367,49 +397,61 @@ impl Language {
             | Language::Macedonian
             | Language::Mongolian
             | Language::Russian
-            | Language::Serbian
             | Language::Ukrainian => hashset!(Alphabet::Cyrillic),
 
+            Language::Azerbaijani => hashset!(Alphabet::Latin, Alphabet::Cyrillic),
+            Language::Serbian => hashset!(Alphabet::Cyrillic, Alphabet::Latin),
+
             Language::Arabic | Language::Persian | Language::Urdu => hashset!(Alphabet::Arabic),
 
             Language::Hindi | Language::Marathi => hashset!(Alphabet::Devanagari),
 
+            Language::Amharic => hashset!(Alphabet::Ethiopic),
             Language::Armenian => hashset!(Alphabet::Armenian),
             Language::Bengali => hashset!(Alphabet::Bengali),
+            Language::Burmese => hashset!(Alphabet::Myanmar),
             Language::Chinese => hashset!(Alphabet::Han),
+            Language::Dhivehi => hashset!(Alphabet::Thaana),
             Language::Georgian => hashset!(Alphabet::Georgian),
             Language::Greek => hashset!(Alphabet::Greek),
             Language::Gujarati => hashset!(Alphabet::Gujarati),
             Language::Hebrew => hashset!(Alphabet::Hebrew),
             Language::Japanese => hashset!(Alphabet::Hiragana, Alphabet::Katakana, Alphabet::Han),
+            Language::Kannada => hashset!(Alphabet::Kannada),
+            Language::Khmer => hashset!(Alphabet::Khmer),
             Language::Korean => hashset!(Alphabet::Hangul),
+            Language::Lao => hashset!(Alphabet::Lao),
+            Language::Malayalam => hashset!(Alphabet::Malayalam),
+            Language::Odia => hashset!(Alphabet::Oriya),
             Language::Punjabi => hashset!(Alphabet::Gurmukhi),
+            Language::Sinhala => hashset!(Alphabet::Sinhala),
             Language::Tamil => hashset!(Alphabet::Tamil),
             Language::Telugu => hashset!(Alphabet::Telugu),
             Language::Thai => hashset!(Alphabet::Thai),
+            Language::Tibetan => hashset!(Alphabet::Tibetan),
         }
     }
 
     pub(crate) fn unique_characters(&self) -> Option<&str> {
         match self {
-            Language::Azerbaijani => Some("Əə"),
+            Language::Azerbaijani => Some("ƏəҜҝҸҹ"),
             Language::Catalan => Some("Ïï"),
             Language::Czech => Some("ĚěŘřŮů"),
             Language::Esperanto => Some("ĈĉĜĝĤĥĴĵŜŝŬŭ"),
             Language::German => Some("ß"),
             Language::Hungarian => Some("ŐőŰű"),
-            Language::Kazakh => Some("ӘәҒғҚқҢңҰұ"),
+            Language::Kazakh => Some("ҚқҢңҰұ"),
             Language::Latvian => Some("ĢģĶķĻļŅņ"),
             Language::Lithuanian => Some("ĖėĮįŲų"),
             Language::Macedonian => Some("ЃѓЅѕЌќЏџ"),
             Language::Marathi => Some("ळ"),
-            Language::Mongolian => Some("ӨөҮү"),
             Language::Polish => Some("ŁłŃńŚśŹź"),
             Language::Romanian => Some("Țţ"),
             Language::Serbian => Some("ЂђЋћ"),
             Language::Slovak => Some("ĹĺĽľŔŕ"),
             Language::Spanish => Some("¿¡"),
             Language::Ukrainian => Some("ҐґЄєЇї"),
+            Language::Urdu => Some("ٹڈڑںےۓ"),
             Language::Vietnamese => Some("ẰằẦầẲẳẨẩẴẵẪẫẮắẤấẠạẶặẬậỀềẺẻỂểẼẽỄễẾếỆệỈỉĨĩỊịƠơỒồỜờỎỏỔổỞởỖỗỠỡỐốỚớỘộỢợƯưỪừỦủỬửŨũỮữỨứỤụỰựỲỳỶỷỸỹỴỵ"),
             Language::Yoruba => Some("Ṣṣ"),
             _ => None,
@@ -441,6 +483,7 @@ mod tests {
             hashset!(
                 Afrikaans,
                 Albanian,
+                Amharic,
                 Arabic,
                 Armenian,
                 Azerbaijani,
@@ -450,11 +493,13 @@ mod tests {
                 Bokmal,
                 Bosnian,
                 Bulgarian,
+                Burmese,
                 Catalan,
                 Chinese,
                 Croatian,
                 Czech,
                 Danish,
+                Dhivehi,
                 Dutch,
                 English,
                 Esperanto,
@@ -474,17 +519,22 @@ mod tests {
                 Irish,
                 Italian,
                 Japanese,
+                Kannada,
                 Kazakh,
+                Khmer,
                 Korean,
+                Lao,
                 Latin,
                 Latvian,
                 Lithuanian,
                 Macedonian,
                 Malay,
+                Malayalam,
                 Maori,
                 Marathi,
                 Mongolian,
                 Nynorsk,
+                Odia,
                 Persian,
                 Polish,
                 Portuguese,
@@ -493,6 +543,7 @@ mod tests {
                 Russian,
                 Serbian,
                 Shona,
+                Sinhala,
                 Slovak,
                 Slovene,
                 Somali,
@@ -504,6 +555,7 @@ mod tests {
                 Tamil,
                 Telugu,
                 Thai,
+                Tibetan,
                 Tsonga,
                 Tswana,
                 Turkish,
@@ -525,6 +577,7 @@ mod tests {
             hashset!(
                 Afrikaans,
                 Albanian,
+                Amharic,
                 Arabic,
                 Armenian,
                 Azerbaijani,
@@ -534,11 +587,13 @@ mod tests {
                 Bokmal,
                 Bosnian,
                 Bulgarian,
+                Burmese,
                 Catalan,
                 Chinese,
                 Croatian,
                 Czech,
                 Danish,
+                Dhivehi,
                 Dutch,
                 English,
                 Esperanto,
@@ -558,16 +613,21 @@ mod tests {
                 Irish,
                 Italian,
                 Japanese,
+                Kannada,
                 Kazakh,
+                Khmer,
                 Korean,
+                Lao,
                 Latvian,
                 Lithuanian,
                 Macedonian,
                 Malay,
+                Malayalam,
                 Maori,
                 Marathi,
                 Mongolian,
                 Nynorsk,
+                Odia,
                 Persian,
                 Polish,
                 Portuguese,
@@ -576,6 +636,7 @@ mod tests {
                 Russian,
                 Serbian,
                 Shona,
+                Sinhala,
                 Slovak,
                 Slovene,
                 Somali,
@@ -587,6 +648,7 @@ mod tests {
                 Tamil,
                 Telugu,
                 Thai,
+                Tibetan,
                 Tsonga,
                 Tswana,
                 Turkish,
@@ -614,7 +676,15 @@ mod tests {
         assert_eq!(
             Language::all_with_cyrillic_script(),
             hashset!(
-                Belarusian, Bulgarian, Kazakh, Macedonian, Mongolian, Russian, Serbian, Ukrainian
+                Azerbaijani,
+                Belarusian,
+                Bulgarian,
+                Kazakh,
+                Macedonian,
+                Mongolian,
+                Russian,
+                Serbian,
+                Ukrainian
             )
         );
     }
@@ -664,6 +734,7 @@ mod tests {
                 Polish,
                 Portuguese,
                 Romanian,
+                Serbian,
                 Shona,
                 Slovak,
                 Slovene,
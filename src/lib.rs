@@ -49,7 +49,8 @@
 //! Compared to other language detection libraries, *Lingua's* focus is on *quality over quantity*,
 //! that is, getting detection right for a small set of languages first before adding new ones.
 //! Currently, 74 languages are supported. They are listed as variants in the
-//! [`Language`](./enum.Language.html) enum.
+//! [`Language`](./enum.Language.html) enum. In addition, 10 languages written in scripts that no
+//! other supported language uses, such as Khmer or Tibetan, are detected by their script alone.
 //!
 //! ## 4. How good is it?
 //!
@@ -151,6 +152,10 @@
 //! returned most of the time as in the example above. This is the return value for cases where
 //! language detection is not reliably possible.
 //!
+//! If the candidates within the minimum relative distance are all closely related languages, such as
+//! Bokmal and Nynorsk, `detect_language_or_group_of` returns their common
+//! [`LanguageGroup`](./enum.LanguageGroup.html) instead, wrapped in a `LanguageOrGroup`.
+//!
 //! ### 6.3 Confidence values
 //!
 //! Knowing about the most likely language is nice but how reliable is the computed likelihood?
@@ -239,20 +244,44 @@ extern crate maplit;
 
 mod alphabet;
 mod builder;
+mod cache;
 mod constant;
 mod detector;
+mod evidence;
 mod fraction;
+mod group;
+mod homoglyph;
+mod incremental;
 mod isocode;
+mod keyboard;
 mod language;
 mod model;
 mod models;
 mod ngram;
+mod normalization;
+mod preprocessor;
+mod proper_noun;
+mod result;
+mod sampling;
+mod segmentation;
+mod transliteration;
 mod writer;
 
+pub use alphabet::{Alphabet, ScriptVariant};
 pub use builder::LanguageDetectorBuilder;
+pub use cache::CacheStatistics;
 pub use detector::LanguageDetector;
+pub use evidence::{EvidenceWeighting, LanguageEvidence};
+pub use group::{LanguageGroup, LanguageOrGroup};
+pub use incremental::IncrementalDetector;
 pub use isocode::{IsoCode639_1, IsoCode639_3};
 pub use language::Language;
+pub use preprocessor::{
+    EmailAddressFilter, EmojiFilter, HashtagFilter, InlineCodeFilter, MentionFilter,
+    TextPreprocessor, UrlFilter,
+};
+pub use result::{DetectionResult, DocumentDetectionResult, Verification};
+pub use sampling::SamplingStrategy;
 pub use writer::{LanguageModelFilesWriter, TestDataFilesWriter};
 
 #[cfg(test)]
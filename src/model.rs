@@ -14,14 +14,17 @@
  * limitations under the License.
  */
 
-use crate::constant::LETTER;
+use crate::constant::{LETTER, MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION};
 use crate::fraction::Fraction;
 use crate::language::Language;
 use crate::ngram::Ngram;
+use crate::normalization::normalize_text;
 use itertools::Itertools;
+use once_cell::sync::OnceCell;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
 
 #[cfg(test)]
 use mockall::automock;
@@ -32,11 +35,28 @@ struct JsonLanguageModel {
     ngrams: BTreeMap<Fraction, String>,
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct JsonWordModel {
+    language: Language,
+    words: BTreeMap<Fraction, String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct JsonPairwiseModel {
+    languages: (Language, Language),
+    words: BTreeMap<String, f64>,
+    ngrams: BTreeMap<String, f64>,
+}
+
+const PAIRWISE_NGRAM_LENGTH: usize = 3;
+
 pub(crate) struct TrainingDataLanguageModel {
     language: Language,
     pub(crate) absolute_frequencies: Option<HashMap<Ngram, u32>>,
     relative_frequencies: Option<HashMap<Ngram, Fraction>>,
     json_relative_frequencies: Option<HashMap<Ngram, f64>>,
+    joint_frequencies: OnceCell<HashMap<Ngram, f64>>,
+    folded_relative_frequencies: OnceCell<HashMap<Ngram, f64>>,
 }
 
 #[cfg_attr(test, automock)]
@@ -62,6 +82,8 @@ impl TrainingDataLanguageModel {
             absolute_frequencies: Some(absolute_frequencies),
             relative_frequencies: Some(relative_frequencies),
             json_relative_frequencies: None,
+            joint_frequencies: OnceCell::new(),
+            folded_relative_frequencies: OnceCell::new(),
         }
     }
 
@@ -81,6 +103,8 @@ impl TrainingDataLanguageModel {
             absolute_frequencies: None,
             relative_frequencies: None,
             json_relative_frequencies: Some(json_relative_frequencies),
+            joint_frequencies: OnceCell::new(),
+            folded_relative_frequencies: OnceCell::new(),
         }
     }
 
@@ -115,6 +139,56 @@ impl TrainingDataLanguageModel {
         }
     }
 
+    /// Returns the probabilities of the ngrams to occur in text, as opposed to their relative
+    /// frequencies which are conditioned on the ngrams' prefixes. They are proportional to the
+    /// ngrams' absolute frequencies in the training data and are derived from the probabilities
+    /// of the prefixes, given by the model of the next lower order. Unigram models do not need
+    /// the probabilities of a lower order.
+    pub(crate) fn get_joint_frequencies(
+        &self,
+        lower_order_joint_frequencies: Option<&'static HashMap<Ngram, f64>>,
+    ) -> &HashMap<Ngram, f64> {
+        self.joint_frequencies
+            .get_or_init(|| self.compute_joint_frequencies(lower_order_joint_frequencies))
+    }
+
+    /// Returns the relative frequency of the given ngram without diacritics, computed from the
+    /// summed up joint frequencies of all ngrams which fold to it and to its prefix.
+    pub(crate) fn get_folded_relative_frequency(
+        &self,
+        ngram: &Ngram,
+        lower_order_joint_frequencies: Option<&'static HashMap<Ngram, f64>>,
+    ) -> f64 {
+        let frequencies = self.folded_relative_frequencies.get_or_init(|| {
+            compute_folded_relative_frequencies(
+                self.get_joint_frequencies(lower_order_joint_frequencies),
+                lower_order_joint_frequencies,
+            )
+        });
+        *frequencies.get(ngram).unwrap_or(&0.0)
+    }
+
+    fn compute_joint_frequencies(
+        &self,
+        lower_order_joint_frequencies: Option<&'static HashMap<Ngram, f64>>,
+    ) -> HashMap<Ngram, f64> {
+        let mut joint_frequencies = hashmap!();
+        if let Some(frequencies) = &self.json_relative_frequencies {
+            for (ngram, frequency) in frequencies {
+                let prefix_frequency = match lower_order_joint_frequencies {
+                    Some(lower_order_frequencies) => *lower_order_frequencies
+                        .get(&get_prefix(ngram))
+                        .unwrap_or(&0.0),
+                    None => 1.0,
+                };
+                if prefix_frequency > 0.0 {
+                    joint_frequencies.insert(ngram.clone(), prefix_frequency * frequency);
+                }
+            }
+        }
+        joint_frequencies
+    }
+
     fn compute_absolute_frequencies<'a>(
         text: &[&'a str],
         ngram_length: usize,
@@ -129,7 +203,11 @@ impl TrainingDataLanguageModel {
         });
 
         for line in text.iter() {
-            let chars = line.to_lowercase().chars().collect_vec();
+            let chars = normalize_text(line).chars().collect_vec();
+
+            if chars.len() < ngram_length {
+                continue;
+            }
 
             for i in 0..=chars.len() - ngram_length {
                 let slice = &chars[i..i + ngram_length].iter().collect::<String>();
@@ -170,8 +248,386 @@ impl TrainingDataLanguageModel {
     }
 }
 
+pub(crate) struct WordLanguageModel {
+    language: Language,
+    relative_frequencies: Option<HashMap<String, Fraction>>,
+    json_relative_frequencies: Option<HashMap<String, f64>>,
+}
+
+impl WordLanguageModel {
+    pub(crate) fn from_text(
+        text: &[&str],
+        language: &Language,
+        char_class: &str,
+        maximum_words: usize,
+    ) -> Self {
+        let absolute_frequencies = Self::compute_absolute_frequencies(text, char_class);
+        let total_word_frequency = absolute_frequencies.values().sum::<u32>();
+
+        let relative_frequencies = absolute_frequencies
+            .iter()
+            .sorted_by(|(first_word, first_count), (second_word, second_count)| {
+                second_count
+                    .cmp(first_count)
+                    .then(first_word.cmp(second_word))
+            })
+            .take(maximum_words)
+            .map(|(word, count)| (word.clone(), Fraction::new(*count, total_word_frequency)))
+            .collect();
+
+        WordLanguageModel {
+            language: language.clone(),
+            relative_frequencies: Some(relative_frequencies),
+            json_relative_frequencies: None,
+        }
+    }
+
+    pub(crate) fn from_json(json: &str) -> Self {
+        let json_word_model = serde_json::from_str::<JsonWordModel>(json).unwrap();
+        let mut json_relative_frequencies = hashmap!();
+
+        for (fraction, words) in json_word_model.words {
+            let floating_point_value = fraction.to_f64();
+            for word in words.split(' ') {
+                json_relative_frequencies.insert(word.to_string(), floating_point_value);
+            }
+        }
+
+        WordLanguageModel {
+            language: json_word_model.language,
+            relative_frequencies: None,
+            json_relative_frequencies: Some(json_relative_frequencies),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        let mut fractions_to_words = hashmap!();
+        for (word, fraction) in self.relative_frequencies.as_ref().unwrap() {
+            let words = fractions_to_words.entry(fraction).or_insert_with(Vec::new);
+            words.push(word);
+        }
+
+        let mut fractions_to_joined_words = btreemap!();
+        for (fraction, words) in fractions_to_words {
+            fractions_to_joined_words.insert(*fraction, words.iter().sorted().join(" "));
+        }
+
+        let model = JsonWordModel {
+            language: self.language.clone(),
+            words: fractions_to_joined_words,
+        };
+
+        serde_json::to_string(&model).unwrap()
+    }
+
+    pub(crate) fn get_relative_frequency(&self, word: &str) -> f64 {
+        match &self.json_relative_frequencies {
+            Some(frequencies) => *frequencies.get(word).unwrap_or(&0.0),
+            None => 0.0,
+        }
+    }
+
+    /// Returns the relative frequency of the rarest word known to this model.
+    /// It serves as an upper bound for the frequency of any unknown word.
+    pub(crate) fn get_minimum_relative_frequency(&self) -> f64 {
+        match &self.json_relative_frequencies {
+            Some(frequencies) => frequencies.values().cloned().fold(1.0, f64::min),
+            None => 1.0,
+        }
+    }
+
+    fn compute_absolute_frequencies(text: &[&str], char_class: &str) -> HashMap<String, u32> {
+        let mut absolute_frequencies = hashmap!();
+        let regex = compile_char_class(char_class);
+
+        for line in text.iter() {
+            for word in split_line_into_words(line, &regex) {
+                let counter = absolute_frequencies.entry(word).or_insert(0);
+                *counter += 1;
+            }
+        }
+
+        absolute_frequencies
+    }
+}
+
+pub(crate) struct PairwiseLanguageModel {
+    first_language: Language,
+    second_language: Language,
+    word_weights: HashMap<String, f64>,
+    ngram_weights: HashMap<String, f64>,
+}
+
+impl PairwiseLanguageModel {
+    /// Creates a discriminative model for two closely related languages.
+    ///
+    /// Each word and each trigram found in the training texts is assigned the logarithmized ratio
+    /// of its smoothed relative frequencies in both languages. Positive weights speak for the
+    /// first language, negative weights for the second one. Only the `maximum_features` most
+    /// distinguishing words and trigrams are kept.
+    pub(crate) fn from_text(
+        first_text: &[&str],
+        first_language: &Language,
+        second_text: &[&str],
+        second_language: &Language,
+        char_class: &str,
+        maximum_features: usize,
+    ) -> Self {
+        if first_language > second_language {
+            return Self::from_text(
+                second_text,
+                second_language,
+                first_text,
+                first_language,
+                char_class,
+                maximum_features,
+            );
+        }
+
+        let regex = compile_char_class(char_class);
+        let (first_words, first_ngrams) = Self::count_features(first_text, &regex);
+        let (second_words, second_ngrams) = Self::count_features(second_text, &regex);
+
+        PairwiseLanguageModel {
+            first_language: first_language.clone(),
+            second_language: second_language.clone(),
+            word_weights: Self::compute_weights(&first_words, &second_words, maximum_features),
+            ngram_weights: Self::compute_weights(&first_ngrams, &second_ngrams, maximum_features),
+        }
+    }
+
+    pub(crate) fn from_json(json: &str) -> Self {
+        let json_pairwise_model = serde_json::from_str::<JsonPairwiseModel>(json).unwrap();
+        let (first_language, second_language) = json_pairwise_model.languages;
+
+        PairwiseLanguageModel {
+            first_language,
+            second_language,
+            word_weights: json_pairwise_model.words.into_iter().collect(),
+            ngram_weights: json_pairwise_model.ngrams.into_iter().collect(),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        let model = JsonPairwiseModel {
+            languages: (self.first_language.clone(), self.second_language.clone()),
+            words: self.word_weights.clone().into_iter().collect(),
+            ngrams: self.ngram_weights.clone().into_iter().collect(),
+        };
+
+        serde_json::to_string(&model).unwrap()
+    }
+
+    pub(crate) fn languages(&self) -> (&Language, &Language) {
+        (&self.first_language, &self.second_language)
+    }
+
+    /// Returns the language that the given words speak for
+    /// or `None` if the evidence is balanced.
+    pub(crate) fn decide(&self, words: &[&str]) -> Option<&Language> {
+        let score = self.compute_score(words);
+
+        if score > 0.0 {
+            Some(&self.first_language)
+        } else if score < 0.0 {
+            Some(&self.second_language)
+        } else {
+            None
+        }
+    }
+
+    fn compute_score(&self, words: &[&str]) -> f64 {
+        let mut score = 0.0;
+
+        for word in words.iter() {
+            score += self.word_weights.get(*word).unwrap_or(&0.0);
+
+            for ngram in Self::split_word_into_ngrams(word) {
+                score += self.ngram_weights.get(&ngram).unwrap_or(&0.0);
+            }
+        }
+
+        score
+    }
+
+    fn count_features(
+        text: &[&str],
+        regex: &Regex,
+    ) -> (HashMap<String, u32>, HashMap<String, u32>) {
+        let mut word_counts = hashmap!();
+        let mut ngram_counts = hashmap!();
+
+        for line in text.iter() {
+            for word in split_line_into_words(line, regex) {
+                for ngram in Self::split_word_into_ngrams(&word) {
+                    let counter = ngram_counts.entry(ngram).or_insert(0);
+                    *counter += 1;
+                }
+                let counter = word_counts.entry(word).or_insert(0);
+                *counter += 1;
+            }
+        }
+
+        (word_counts, ngram_counts)
+    }
+
+    fn compute_weights(
+        first_counts: &HashMap<String, u32>,
+        second_counts: &HashMap<String, u32>,
+        maximum_features: usize,
+    ) -> HashMap<String, f64> {
+        let first_total = first_counts.values().sum::<u32>() as f64;
+        let second_total = second_counts.values().sum::<u32>() as f64;
+        let vocabulary = first_counts
+            .keys()
+            .chain(second_counts.keys())
+            .collect::<HashSet<_>>();
+        let vocabulary_size = vocabulary.len() as f64;
+
+        vocabulary
+            .into_iter()
+            .map(|feature| {
+                let first_count = *first_counts.get(feature).unwrap_or(&0) as f64;
+                let second_count = *second_counts.get(feature).unwrap_or(&0) as f64;
+                let first_probability = (first_count + 1.0) / (first_total + vocabulary_size);
+                let second_probability = (second_count + 1.0) / (second_total + vocabulary_size);
+                let weight = (first_probability / second_probability).ln();
+
+                (feature, (weight * 10000.0).round() / 10000.0)
+            })
+            .filter(|(_, weight)| *weight != 0.0)
+            .sorted_by(
+                |(first_feature, first_weight), (second_feature, second_weight)| {
+                    second_weight
+                        .abs()
+                        .partial_cmp(&first_weight.abs())
+                        .unwrap()
+                        .then(first_feature.cmp(second_feature))
+                },
+            )
+            .take(maximum_features)
+            .map(|(feature, weight)| (feature.clone(), weight))
+            .collect()
+    }
+
+    fn split_word_into_ngrams(word: &str) -> Vec<String> {
+        let chars = word.chars().collect_vec();
+
+        if chars.len() < PAIRWISE_NGRAM_LENGTH {
+            return vec![];
+        }
+
+        chars
+            .windows(PAIRWISE_NGRAM_LENGTH)
+            .map(|window| window.iter().collect())
+            .collect()
+    }
+}
+
+/// Divides the summed up joint frequencies of all ngrams with the same folded form by those of
+/// their folded prefixes. As folding keeps the length of an ngram, the ngrams folding to the same
+/// form have prefixes which fold to the same form as well, so the result never exceeds 1.
+fn compute_folded_relative_frequencies(
+    joint_frequencies: &HashMap<Ngram, f64>,
+    lower_order_joint_frequencies: Option<&HashMap<Ngram, f64>>,
+) -> HashMap<Ngram, f64> {
+    let folded_joint_frequencies = fold_frequencies(joint_frequencies);
+
+    match lower_order_joint_frequencies {
+        Some(lower_order_frequencies) => {
+            let folded_lower_order_frequencies = fold_frequencies(lower_order_frequencies);
+            folded_joint_frequencies
+                .into_iter()
+                .filter_map(|(ngram, frequency)| {
+                    folded_lower_order_frequencies
+                        .get(&get_prefix(&ngram))
+                        .map(|prefix_frequency| (ngram, frequency / prefix_frequency))
+                })
+                .collect()
+        }
+        None => folded_joint_frequencies,
+    }
+}
+
+fn fold_frequencies(frequencies: &HashMap<Ngram, f64>) -> HashMap<Ngram, f64> {
+    let mut folded_frequencies = hashmap!();
+    for (ngram, frequency) in frequencies {
+        let folded_frequency = folded_frequencies.entry(fold_ngram(ngram)).or_insert(0.0);
+        *folded_frequency += frequency;
+    }
+    folded_frequencies
+}
+
+/// Folds the diacritics of each character of the given ngram separately, leaving characters
+/// untouched which would not fold to a single character, so that the ngram keeps its length.
+fn fold_ngram(ngram: &Ngram) -> Ngram {
+    let folded_ngram = ngram
+        .value
+        .chars()
+        .map(|chr| {
+            let folded_chr = fold_diacritics(&chr.to_string());
+            if folded_chr.chars().count() == 1 {
+                folded_chr
+            } else {
+                chr.to_string()
+            }
+        })
+        .collect::<String>();
+    Ngram::new(&folded_ngram)
+}
+
+fn get_prefix(ngram: &Ngram) -> Ngram {
+    let chars = ngram.value.chars().collect_vec();
+    Ngram::new(&chars[..chars.len() - 1].iter().collect::<String>())
+}
+
+/// Removes the diacritics from all precomposed characters of the given text
+/// by decomposing them canonically and dropping the resulting combining marks.
+/// Characters without diacritics are left untouched.
+pub(crate) fn fold_diacritics(text: &str) -> String {
+    let mut folded_text = String::with_capacity(text.len());
+    for chr in text.chars() {
+        let mut decomposition = vec![];
+        decompose_canonical(chr, |it| decomposition.push(it));
+
+        if decomposition.len() > 1 && decomposition.iter().any(|&it| is_combining_mark(it)) {
+            folded_text.extend(
+                decomposition
+                    .into_iter()
+                    .filter(|&it| !is_combining_mark(it)),
+            );
+        } else {
+            folded_text.push(chr);
+        }
+    }
+    folded_text
+}
+
+fn compile_char_class(char_class: &str) -> Regex {
+    Regex::new(&format!("^[{}]+$", char_class)).unwrap_or_else(|_| {
+        panic!(
+            "The character class '{}' cannot be compiled to a valid regular expression",
+            char_class
+        )
+    })
+}
+
+fn split_line_into_words(line: &str, regex: &Regex) -> Vec<String> {
+    let normalized = normalize_text(line);
+    let without_punctuation = PUNCTUATION.replace_all(&normalized, "");
+    let without_numbers = NUMBERS.replace_all(&without_punctuation, "");
+    let normalized_whitespace = MULTIPLE_WHITESPACE.replace_all(&without_numbers, " ");
+
+    normalized_whitespace
+        .split(' ')
+        .filter(|word| regex.is_match(word))
+        .map(|word| word.to_string())
+        .collect()
+}
+
 pub(crate) struct TestDataLanguageModel {
     pub(crate) ngrams: HashSet<Ngram>,
+    pub(crate) ngram_counts: HashMap<Ngram, u32>,
 }
 
 impl TestDataLanguageModel {
@@ -180,25 +636,53 @@ impl TestDataLanguageModel {
             panic!("ngram length {} is not in range 1..6", ngram_length);
         }
 
-        let mut ngrams = hashset!();
+        let mut ngram_counts = hashmap!();
         let chars = text.chars().collect_vec();
 
         if chars.len() >= ngram_length {
             for i in 0..=chars.len() - ngram_length {
                 let slice = &chars[i..i + ngram_length].iter().collect::<String>();
                 if LETTER.is_match(slice) {
-                    ngrams.insert(Ngram::new(slice));
+                    *ngram_counts.entry(Ngram::new(slice)).or_insert(0) += 1;
                 }
             }
         }
 
-        Self { ngrams }
+        let ngrams = ngram_counts.keys().cloned().collect();
+
+        Self {
+            ngrams,
+            ngram_counts,
+        }
+    }
+
+    /// Splits this model into models of at most the given number of ngrams each.
+    /// The ngrams are distributed in alphabetical order so that the chunks are reproducible.
+    pub(crate) fn split_into_chunks(&self, chunk_size: usize) -> Vec<TestDataLanguageModel> {
+        self.ngrams
+            .iter()
+            .sorted_by(|first, second| first.value.cmp(&second.value))
+            .chunks(chunk_size)
+            .into_iter()
+            .map(|chunk| {
+                let ngrams = chunk.cloned().collect::<HashSet<_>>();
+                let ngram_counts = ngrams
+                    .iter()
+                    .map(|ngram| (ngram.clone(), self.ngram_counts[ngram]))
+                    .collect();
+                Self {
+                    ngrams,
+                    ngram_counts,
+                }
+            })
+            .collect_vec()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use float_cmp::approx_eq;
     use itertools::Itertools;
     use rstest::*;
 
@@ -469,6 +953,23 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_ngram_model_creation_skips_lines_shorter_than_ngrams() {
+            // the decomposed é is composed into a single character by normalization
+            let model = TrainingDataLanguageModel::from_text(
+                &["e\u{301}", "ab"],
+                &Language::French,
+                2,
+                "\\p{L}",
+                &hashmap!(),
+            );
+
+            assert_eq!(
+                model.absolute_frequencies,
+                Some(hashmap!(Ngram::new("ab") => 1))
+            );
+        }
+
         #[test]
         fn test_model_serializer_and_deserializer() {
             let model = TrainingDataLanguageModel {
@@ -476,6 +977,8 @@ mod tests {
                 absolute_frequencies: None,
                 relative_frequencies: Some(expected_unigram_relative_frequencies()),
                 json_relative_frequencies: None,
+                joint_frequencies: OnceCell::new(),
+                folded_relative_frequencies: OnceCell::new(),
             };
             let deserialized = TrainingDataLanguageModel::from_json(&model.to_json());
 
@@ -487,6 +990,182 @@ mod tests {
                 Some(expected_unigram_json_relative_frequencies())
             );
         }
+
+        #[test]
+        fn test_folded_relative_frequencies() {
+            let model = TrainingDataLanguageModel::from_json(
+                r#"{"language":"FRENCH","ngrams":{"1/10":"é","1/5":"e","1/2":"è","1/20":"a"}}"#,
+            );
+
+            assert!(approx_eq!(
+                f64,
+                model.get_folded_relative_frequency(&Ngram::new("e"), None),
+                0.8,
+                ulps = 2
+            ));
+            assert!(approx_eq!(
+                f64,
+                model.get_folded_relative_frequency(&Ngram::new("a"), None),
+                0.05,
+                ulps = 2
+            ));
+            assert_eq!(
+                model.get_folded_relative_frequency(&Ngram::new("é"), None),
+                0.0
+            );
+        }
+
+        #[test]
+        fn test_folded_relative_frequencies_of_higher_order() {
+            static UNIGRAM_MODEL: OnceCell<TrainingDataLanguageModel> = OnceCell::new();
+
+            let unigram_model = UNIGRAM_MODEL.get_or_init(|| {
+                TrainingDataLanguageModel::from_json(
+                    r#"{"language":"FRENCH","ngrams":{"1/2":"e","1/4":"é t"}}"#,
+                )
+            });
+            let bigram_model = TrainingDataLanguageModel::from_json(
+                r#"{"language":"FRENCH","ngrams":{"1/2":"et","1/1":"ét","1/4":"es"}}"#,
+            );
+            let unigram_joint_frequencies = unigram_model.get_joint_frequencies(None);
+
+            assert!(approx_eq!(
+                f64,
+                bigram_model.get_joint_frequencies(Some(unigram_joint_frequencies))
+                    [&Ngram::new("ét")],
+                0.25,
+                ulps = 2
+            ));
+
+            // (1/2 * 1/2 + 1/4 * 1/1) / (1/2 + 1/4) instead of the sum 1/2 + 1/1
+            assert!(approx_eq!(
+                f64,
+                bigram_model.get_folded_relative_frequency(
+                    &Ngram::new("et"),
+                    Some(unigram_joint_frequencies)
+                ),
+                2.0 / 3.0,
+                ulps = 2
+            ));
+            assert!(approx_eq!(
+                f64,
+                bigram_model.get_folded_relative_frequency(
+                    &Ngram::new("es"),
+                    Some(unigram_joint_frequencies)
+                ),
+                1.0 / 6.0,
+                ulps = 2
+            ));
+        }
+
+        #[rstest(
+            text,
+            expected_folded_text,
+            case("ça", "ca"),
+            case("straße", "straße"),
+            case("žluťoučký", "zlutoucky"),
+            case("ελληνικά", "ελληνικα"),
+            case("한국어", "한국어")
+        )]
+        fn test_diacritics_are_folded_correctly(text: &str, expected_folded_text: &str) {
+            assert_eq!(fold_diacritics(text), expected_folded_text);
+        }
+    }
+
+    mod word_data {
+        use super::*;
+
+        #[test]
+        fn test_word_model_creation() {
+            let model = WordLanguageModel::from_text(
+                &TEXT.trim().lines().collect::<Vec<_>>(),
+                &Language::English,
+                "\\p{L}",
+                3,
+            );
+
+            assert_eq!(model.language, Language::English);
+            assert_eq!(
+                model.relative_frequencies,
+                Some(hashmap!(
+                    "in".to_string() => Fraction::new(1, 11),
+                    "are".to_string() => Fraction::new(1, 22),
+                    "by".to_string() => Fraction::new(1, 22)
+                ))
+            );
+        }
+
+        #[test]
+        fn test_word_model_serializer_and_deserializer() {
+            let model = WordLanguageModel::from_text(
+                &TEXT.trim().lines().collect::<Vec<_>>(),
+                &Language::English,
+                "\\p{L}",
+                3,
+            );
+            let json = model.to_json();
+
+            assert_eq!(
+                json,
+                r#"{"language":"ENGLISH","words":{"1/11":"in","1/22":"are by"}}"#
+            );
+
+            let deserialized = WordLanguageModel::from_json(&json);
+
+            assert_eq!(deserialized.language, Language::English);
+            assert_eq!(deserialized.get_relative_frequency("in"), 1.0 / 11.0);
+            assert_eq!(deserialized.get_relative_frequency("by"), 1.0 / 22.0);
+            assert_eq!(deserialized.get_relative_frequency("production"), 0.0);
+            assert_eq!(deserialized.get_minimum_relative_frequency(), 1.0 / 22.0);
+        }
+    }
+
+    mod pairwise_data {
+        use super::*;
+
+        #[test]
+        fn test_pairwise_model_creation() {
+            let model = PairwiseLanguageModel::from_text(
+                &["Što je to?", "Što radiš?"],
+                &Language::Croatian,
+                &["Šta je to?", "Šta radiš?"],
+                &Language::Bosnian,
+                "\\p{L}",
+                2,
+            );
+
+            assert_eq!(model.languages(), (&Language::Bosnian, &Language::Croatian));
+            assert_eq!(model.decide(&["šta", "je"]), Some(&Language::Bosnian));
+            assert_eq!(model.decide(&["što", "je"]), Some(&Language::Croatian));
+            assert_eq!(model.decide(&["radiš"]), None);
+        }
+
+        #[test]
+        fn test_pairwise_model_serializer_and_deserializer() {
+            let model = PairwiseLanguageModel::from_text(
+                &["Što je to?", "Što radiš?"],
+                &Language::Croatian,
+                &["Šta je to?", "Šta radiš?"],
+                &Language::Bosnian,
+                "\\p{L}",
+                1,
+            );
+            let json = model.to_json();
+
+            assert_eq!(
+                json,
+                r#"{"languages":["BOSNIAN","CROATIAN"],"words":{"šta":1.0986},"ngrams":{"šta":1.0986}}"#
+            );
+
+            let deserialized = PairwiseLanguageModel::from_json(&json);
+
+            assert_eq!(
+                deserialized.languages(),
+                (&Language::Bosnian, &Language::Croatian)
+            );
+            assert_eq!(deserialized.decide(&["šta"]), Some(&Language::Bosnian));
+            assert_eq!(deserialized.decide(&["što"]), None);
+        }
     }
 
     mod test_data {
@@ -558,5 +1237,42 @@ mod tests {
             let model = TestDataLanguageModel::from(&TEXT.to_lowercase(), ngram_length);
             assert_eq!(model.ngrams, expected_ngrams);
         }
+
+        #[test]
+        fn test_ngram_counts() {
+            let model = TestDataLanguageModel::from("alles allein", 2);
+            assert_eq!(model.ngram_counts[&Ngram::new("al")], 2);
+            assert_eq!(model.ngram_counts[&Ngram::new("ll")], 2);
+            assert_eq!(model.ngram_counts[&Ngram::new("es")], 1);
+            assert_eq!(model.ngram_counts.len(), model.ngrams.len());
+        }
+
+        #[test]
+        fn test_model_is_split_into_chunks() {
+            let model = TestDataLanguageModel::from("alles allein", 2);
+            let chunks = model.split_into_chunks(4);
+
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(chunks[0].ngrams.len(), 4);
+            assert_eq!(chunks[1].ngrams.len(), 2);
+            assert_eq!(chunks[0].ngram_counts[&Ngram::new("al")], 2);
+            assert_eq!(
+                chunks
+                    .iter()
+                    .flat_map(|it| it.ngrams.clone())
+                    .collect::<HashSet<_>>(),
+                model.ngrams
+            );
+        }
+
+        #[test]
+        fn test_ngram_model_creation_without_punctuation() {
+            let model = TestDataLanguageModel::from("l'homme bien-aimé", 2);
+            assert!(model.ngrams.contains(&Ngram::new("ho")));
+            assert!(!model.ngrams.contains(&Ngram::new("l'")));
+            assert!(!model.ngrams.contains(&Ngram::new("'h")));
+            assert!(!model.ngrams.contains(&Ngram::new("n-")));
+            assert!(!model.ngrams.contains(&Ngram::new("-a")));
+        }
     }
 }
@@ -108,9 +108,11 @@ cfg_if! {
 
 pub(crate) mod bigram_models;
 pub(crate) mod fivegram_models;
+pub(crate) mod pairwise_models;
 pub(crate) mod quadrigram_models;
 pub(crate) mod trigram_models;
 pub(crate) mod unigram_models;
+pub(crate) mod word_models;
 
 pub(crate) type LazyTrainingDataLanguageModel = &'static TrainingDataLanguageModel;
 pub(crate) type LanguageToNgramsMappingCell =
@@ -121,93 +123,108 @@ pub(crate) type LazyLanguageToNgramsMapping =
 fn load_json(language: Language, ngram_length: u32) -> std::io::Result<String> {
     let ngram_name = Ngram::get_ngram_name_by_length(ngram_length);
     let file_path = format!("{}s.json.zip", ngram_name);
-    let directory = get_language_models_directory(language);
-    let zip_file = directory.get_file(file_path).unwrap();
+    load_zipped_json(language, &file_path).unwrap()
+}
+
+fn load_zipped_json(language: Language, file_path: &str) -> Option<std::io::Result<String>> {
+    let directory = get_language_models_directory(language)?;
+    let zip_file = directory.get_file(file_path)?;
     let zip_file_reader = Cursor::new(zip_file.contents());
     let mut archive = ZipArchive::new(zip_file_reader).unwrap();
     let mut json_file = archive.by_index(0).unwrap();
     let mut json = String::new();
-    json_file.read_to_string(&mut json)?;
-    Ok(json)
+    Some(json_file.read_to_string(&mut json).map(|_| json))
 }
 
-fn get_language_models_directory(language: Language) -> Dir<'static> {
+fn get_language_models_directory(language: Language) -> Option<Dir<'static>> {
     match language {
-        Language::Afrikaans => AFRIKAANS_MODELS_DIRECTORY,
-        Language::Albanian => ALBANIAN_MODELS_DIRECTORY,
-        Language::Arabic => ARABIC_MODELS_DIRECTORY,
-        Language::Armenian => ARMENIAN_MODELS_DIRECTORY,
-        Language::Azerbaijani => AZERBAIJANI_MODELS_DIRECTORY,
-        Language::Basque => BASQUE_MODELS_DIRECTORY,
-        Language::Belarusian => BELARUSIAN_MODELS_DIRECTORY,
-        Language::Bengali => BENGALI_MODELS_DIRECTORY,
-        Language::Bokmal => BOKMAL_MODELS_DIRECTORY,
-        Language::Bosnian => BOSNIAN_MODELS_DIRECTORY,
-        Language::Bulgarian => BULGARIAN_MODELS_DIRECTORY,
-        Language::Catalan => CATALAN_MODELS_DIRECTORY,
-        Language::Chinese => CHINESE_MODELS_DIRECTORY,
-        Language::Croatian => CROATIAN_MODELS_DIRECTORY,
-        Language::Czech => CZECH_MODELS_DIRECTORY,
-        Language::Danish => DANISH_MODELS_DIRECTORY,
-        Language::Dutch => DUTCH_MODELS_DIRECTORY,
-        Language::English => ENGLISH_MODELS_DIRECTORY,
-        Language::Esperanto => ESPERANTO_MODELS_DIRECTORY,
-        Language::Estonian => ESTONIAN_MODELS_DIRECTORY,
-        Language::Finnish => FINNISH_MODELS_DIRECTORY,
-        Language::French => FRENCH_MODELS_DIRECTORY,
-        Language::Ganda => GANDA_MODELS_DIRECTORY,
-        Language::Georgian => GEORGIAN_MODELS_DIRECTORY,
-        Language::German => GERMAN_MODELS_DIRECTORY,
-        Language::Greek => GREEK_MODELS_DIRECTORY,
-        Language::Gujarati => GUJARATI_MODELS_DIRECTORY,
-        Language::Hebrew => HEBREW_MODELS_DIRECTORY,
-        Language::Hindi => HINDI_MODELS_DIRECTORY,
-        Language::Hungarian => HUNGARIAN_MODELS_DIRECTORY,
-        Language::Icelandic => ICELANDIC_MODELS_DIRECTORY,
-        Language::Indonesian => INDONESIAN_MODELS_DIRECTORY,
-        Language::Irish => IRISH_MODELS_DIRECTORY,
-        Language::Italian => ITALIAN_MODELS_DIRECTORY,
-        Language::Japanese => JAPANESE_MODELS_DIRECTORY,
-        Language::Kazakh => KAZAKH_MODELS_DIRECTORY,
-        Language::Korean => KOREAN_MODELS_DIRECTORY,
-        Language::Latin => LATIN_MODELS_DIRECTORY,
-        Language::Latvian => LATVIAN_MODELS_DIRECTORY,
-        Language::Lithuanian => LITHUANIAN_MODELS_DIRECTORY,
-        Language::Macedonian => MACEDONIAN_MODELS_DIRECTORY,
-        Language::Malay => MALAY_MODELS_DIRECTORY,
-        Language::Maori => MAORI_MODELS_DIRECTORY,
-        Language::Marathi => MARATHI_MODELS_DIRECTORY,
-        Language::Mongolian => MONGOLIAN_MODELS_DIRECTORY,
-        Language::Nynorsk => NYNORSK_MODELS_DIRECTORY,
-        Language::Persian => PERSIAN_MODELS_DIRECTORY,
-        Language::Polish => POLISH_MODELS_DIRECTORY,
-        Language::Portuguese => PORTUGUESE_MODELS_DIRECTORY,
-        Language::Punjabi => PUNJABI_MODELS_DIRECTORY,
-        Language::Romanian => ROMANIAN_MODELS_DIRECTORY,
-        Language::Russian => RUSSIAN_MODELS_DIRECTORY,
-        Language::Serbian => SERBIAN_MODELS_DIRECTORY,
-        Language::Shona => SHONA_MODELS_DIRECTORY,
-        Language::Slovak => SLOVAK_MODELS_DIRECTORY,
-        Language::Slovene => SLOVENE_MODELS_DIRECTORY,
-        Language::Somali => SOMALI_MODELS_DIRECTORY,
-        Language::Sotho => SOTHO_MODELS_DIRECTORY,
-        Language::Spanish => SPANISH_MODELS_DIRECTORY,
-        Language::Swahili => SWAHILI_MODELS_DIRECTORY,
-        Language::Swedish => SWEDISH_MODELS_DIRECTORY,
-        Language::Tagalog => TAGALOG_MODELS_DIRECTORY,
-        Language::Tamil => TAMIL_MODELS_DIRECTORY,
-        Language::Telugu => TELUGU_MODELS_DIRECTORY,
-        Language::Thai => THAI_MODELS_DIRECTORY,
-        Language::Tsonga => TSONGA_MODELS_DIRECTORY,
-        Language::Tswana => TSWANA_MODELS_DIRECTORY,
-        Language::Turkish => TURKISH_MODELS_DIRECTORY,
-        Language::Ukrainian => UKRAINIAN_MODELS_DIRECTORY,
-        Language::Urdu => URDU_MODELS_DIRECTORY,
-        Language::Vietnamese => VIETNAMESE_MODELS_DIRECTORY,
-        Language::Welsh => WELSH_MODELS_DIRECTORY,
-        Language::Xhosa => XHOSA_MODELS_DIRECTORY,
-        Language::Yoruba => YORUBA_MODELS_DIRECTORY,
-        Language::Zulu => ZULU_MODELS_DIRECTORY,
+        Language::Afrikaans => Some(AFRIKAANS_MODELS_DIRECTORY),
+        Language::Albanian => Some(ALBANIAN_MODELS_DIRECTORY),
+        Language::Arabic => Some(ARABIC_MODELS_DIRECTORY),
+        Language::Armenian => Some(ARMENIAN_MODELS_DIRECTORY),
+        Language::Azerbaijani => Some(AZERBAIJANI_MODELS_DIRECTORY),
+        Language::Basque => Some(BASQUE_MODELS_DIRECTORY),
+        Language::Belarusian => Some(BELARUSIAN_MODELS_DIRECTORY),
+        Language::Bengali => Some(BENGALI_MODELS_DIRECTORY),
+        Language::Bokmal => Some(BOKMAL_MODELS_DIRECTORY),
+        Language::Bosnian => Some(BOSNIAN_MODELS_DIRECTORY),
+        Language::Bulgarian => Some(BULGARIAN_MODELS_DIRECTORY),
+        Language::Catalan => Some(CATALAN_MODELS_DIRECTORY),
+        Language::Chinese => Some(CHINESE_MODELS_DIRECTORY),
+        Language::Croatian => Some(CROATIAN_MODELS_DIRECTORY),
+        Language::Czech => Some(CZECH_MODELS_DIRECTORY),
+        Language::Danish => Some(DANISH_MODELS_DIRECTORY),
+        Language::Dutch => Some(DUTCH_MODELS_DIRECTORY),
+        Language::English => Some(ENGLISH_MODELS_DIRECTORY),
+        Language::Esperanto => Some(ESPERANTO_MODELS_DIRECTORY),
+        Language::Estonian => Some(ESTONIAN_MODELS_DIRECTORY),
+        Language::Finnish => Some(FINNISH_MODELS_DIRECTORY),
+        Language::French => Some(FRENCH_MODELS_DIRECTORY),
+        Language::Ganda => Some(GANDA_MODELS_DIRECTORY),
+        Language::Georgian => Some(GEORGIAN_MODELS_DIRECTORY),
+        Language::German => Some(GERMAN_MODELS_DIRECTORY),
+        Language::Greek => Some(GREEK_MODELS_DIRECTORY),
+        Language::Gujarati => Some(GUJARATI_MODELS_DIRECTORY),
+        Language::Hebrew => Some(HEBREW_MODELS_DIRECTORY),
+        Language::Hindi => Some(HINDI_MODELS_DIRECTORY),
+        Language::Hungarian => Some(HUNGARIAN_MODELS_DIRECTORY),
+        Language::Icelandic => Some(ICELANDIC_MODELS_DIRECTORY),
+        Language::Indonesian => Some(INDONESIAN_MODELS_DIRECTORY),
+        Language::Irish => Some(IRISH_MODELS_DIRECTORY),
+        Language::Italian => Some(ITALIAN_MODELS_DIRECTORY),
+        Language::Japanese => Some(JAPANESE_MODELS_DIRECTORY),
+        Language::Kazakh => Some(KAZAKH_MODELS_DIRECTORY),
+        Language::Korean => Some(KOREAN_MODELS_DIRECTORY),
+        Language::Latin => Some(LATIN_MODELS_DIRECTORY),
+        Language::Latvian => Some(LATVIAN_MODELS_DIRECTORY),
+        Language::Lithuanian => Some(LITHUANIAN_MODELS_DIRECTORY),
+        Language::Macedonian => Some(MACEDONIAN_MODELS_DIRECTORY),
+        Language::Malay => Some(MALAY_MODELS_DIRECTORY),
+        Language::Maori => Some(MAORI_MODELS_DIRECTORY),
+        Language::Marathi => Some(MARATHI_MODELS_DIRECTORY),
+        Language::Mongolian => Some(MONGOLIAN_MODELS_DIRECTORY),
+        Language::Nynorsk => Some(NYNORSK_MODELS_DIRECTORY),
+        Language::Persian => Some(PERSIAN_MODELS_DIRECTORY),
+        Language::Polish => Some(POLISH_MODELS_DIRECTORY),
+        Language::Portuguese => Some(PORTUGUESE_MODELS_DIRECTORY),
+        Language::Punjabi => Some(PUNJABI_MODELS_DIRECTORY),
+        Language::Romanian => Some(ROMANIAN_MODELS_DIRECTORY),
+        Language::Russian => Some(RUSSIAN_MODELS_DIRECTORY),
+        Language::Serbian => Some(SERBIAN_MODELS_DIRECTORY),
+        Language::Shona => Some(SHONA_MODELS_DIRECTORY),
+        Language::Slovak => Some(SLOVAK_MODELS_DIRECTORY),
+        Language::Slovene => Some(SLOVENE_MODELS_DIRECTORY),
+        Language::Somali => Some(SOMALI_MODELS_DIRECTORY),
+        Language::Sotho => Some(SOTHO_MODELS_DIRECTORY),
+        Language::Spanish => Some(SPANISH_MODELS_DIRECTORY),
+        Language::Swahili => Some(SWAHILI_MODELS_DIRECTORY),
+        Language::Swedish => Some(SWEDISH_MODELS_DIRECTORY),
+        Language::Tagalog => Some(TAGALOG_MODELS_DIRECTORY),
+        Language::Tamil => Some(TAMIL_MODELS_DIRECTORY),
+        Language::Telugu => Some(TELUGU_MODELS_DIRECTORY),
+        Language::Thai => Some(THAI_MODELS_DIRECTORY),
+        Language::Tsonga => Some(TSONGA_MODELS_DIRECTORY),
+        Language::Tswana => Some(TSWANA_MODELS_DIRECTORY),
+        Language::Turkish => Some(TURKISH_MODELS_DIRECTORY),
+        Language::Ukrainian => Some(UKRAINIAN_MODELS_DIRECTORY),
+        Language::Urdu => Some(URDU_MODELS_DIRECTORY),
+        Language::Vietnamese => Some(VIETNAMESE_MODELS_DIRECTORY),
+        Language::Welsh => Some(WELSH_MODELS_DIRECTORY),
+        Language::Xhosa => Some(XHOSA_MODELS_DIRECTORY),
+        Language::Yoruba => Some(YORUBA_MODELS_DIRECTORY),
+        Language::Zulu => Some(ZULU_MODELS_DIRECTORY),
+
+        // These languages are detected by their unique alphabets alone.
+        Language::Amharic
+        | Language::Burmese
+        | Language::Dhivehi
+        | Language::Kannada
+        | Language::Khmer
+        | Language::Lao
+        | Language::Malayalam
+        | Language::Odia
+        | Language::Sinhala
+        | Language::Tibetan => None,
     }
 }
 
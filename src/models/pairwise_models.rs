@@ -0,0 +1,74 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::PairwiseLanguageModel;
+use crate::Language;
+use include_dir::{include_dir, Dir};
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+const PAIRWISE_MODELS_DIRECTORY: Dir = include_dir!("pairwise-models");
+
+pub(crate) type LanguagePairToModelMappingCell =
+    OnceCell<HashMap<(Language, Language), PairwiseLanguageModel>>;
+pub(crate) type LazyLanguagePairToModelMapping =
+    &'static HashMap<(Language, Language), PairwiseLanguageModel>;
+
+/// The returned mapping is keyed by both languages of a model in ascending order.
+pub(crate) fn pairwise_models() -> LazyLanguagePairToModelMapping {
+    static PAIRWISE_MODELS: LanguagePairToModelMappingCell = OnceCell::new();
+    PAIRWISE_MODELS.get_or_init(|| {
+        PAIRWISE_MODELS_DIRECTORY
+            .files()
+            .iter()
+            .filter(|file| file.path().to_string_lossy().ends_with(".json.zip"))
+            .map(|file| {
+                let zip_file_reader = Cursor::new(file.contents());
+                let mut archive = ZipArchive::new(zip_file_reader).unwrap();
+                let mut json_file = archive.by_index(0).unwrap();
+                let mut json = String::new();
+                json_file.read_to_string(&mut json).unwrap();
+
+                let model = PairwiseLanguageModel::from_json(&json);
+                let (first_language, second_language) = model.languages();
+                ((first_language.clone(), second_language.clone()), model)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language::*;
+
+    #[test]
+    fn assert_shipped_pairwise_models_are_loaded() {
+        let models = pairwise_models();
+
+        assert_eq!(models.len(), 4);
+        assert!(models.contains_key(&(Bokmal, Danish)));
+        assert!(models.contains_key(&(Indonesian, Malay)));
+        assert!(!models.contains_key(&(Bosnian, Croatian)));
+
+        let model = &models[&(Indonesian, Malay)];
+
+        assert_eq!(model.decide(&["uang", "kantor"]), Some(&Indonesian));
+        assert_eq!(model.decide(&["wang", "pejabat"]), Some(&Malay));
+    }
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::WordLanguageModel;
+use crate::models::load_zipped_json;
+use crate::Language;
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+
+pub(crate) type LanguageToWordsMappingCell = OnceCell<HashMap<Language, WordLanguageModel>>;
+pub(crate) type LazyLanguageToWordsMapping = &'static HashMap<Language, WordLanguageModel>;
+
+/// Word models are optional. Only those languages whose model crate
+/// ships a `words.json.zip` file are part of the returned mapping.
+pub(crate) fn word_models() -> LazyLanguageToWordsMapping {
+    static WORD_MODELS: LanguageToWordsMappingCell = OnceCell::new();
+    WORD_MODELS.get_or_init(|| {
+        Language::iter()
+            .filter_map(|language| {
+                load_words(language.clone())
+                    .map(|json| (language, WordLanguageModel::from_json(&json)))
+            })
+            .collect()
+    })
+}
+
+fn load_words(language: Language) -> Option<String> {
+    load_zipped_json(language, "words.json.zip").map(|result| result.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_word_models_are_loaded_for_languages_written_with_spaces() {
+        let models = word_models();
+
+        assert_eq!(models.len(), 72);
+        assert!(!models.contains_key(&Language::Chinese));
+        assert!(!models.contains_key(&Language::Japanese));
+        assert!(!models.contains_key(&Language::Thai));
+
+        let english_model = &models[&Language::English];
+        let german_model = &models[&Language::German];
+
+        assert!(english_model.get_relative_frequency("the") > 0.01);
+        assert!(german_model.get_relative_frequency("und") > 0.01);
+        assert!(
+            english_model.get_relative_frequency("the")
+                > german_model.get_relative_frequency("the")
+        );
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::constant::FINAL_SIGMA;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalizes the given text in the same way for training the language models
+/// and for detecting the language of input text, so that both see identical characters.
+///
+/// The text is brought into Unicode normalization form NFKC, which composes decomposed
+/// diacritics and converts compatibility characters such as full-width Latin letters.
+/// Zero-width and bidirectional control characters are removed. Finally, the text is
+/// lowercased and word-final Greek sigmas are converted into their final form.
+///
+/// Lowercasing does not depend on the language, as explained for `fold_case`.
+pub(crate) fn normalize_text(text: &str) -> String {
+    let composed_text = text
+        .chars()
+        .filter(|&it| !is_invisible_control_character(it))
+        .nfkc()
+        .collect::<String>();
+
+    let lowercased_text = fold_case(&composed_text);
+
+    FINAL_SIGMA.replace_all(&lowercased_text, "ς").to_string()
+}
+
+/// Lowercases the given text in the same way for all languages.
+///
+/// Turkish and Azerbaijani lowercase *I* to the dotless *ı*, but this rule is not applied on
+/// purpose. The language of input text is not known before it has been detected, and the bundled
+/// models have been trained with language-independent lowercasing as well. Applying the rule to
+/// the training data only would produce ngrams which no input text ever contains, so *I* becomes
+/// *i* for every language. The dotted *İ* becomes *i* without the combining dot which its default
+/// lowercase form has, so that it matches the *i* of the models.
+fn fold_case(text: &str) -> String {
+    let mut folded_text = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            'İ' => folded_text.push('i'),
+            _ => folded_text.extend(character.to_lowercase()),
+        }
+    }
+
+    folded_text
+}
+
+fn is_invisible_control_character(character: char) -> bool {
+    matches!(
+        character,
+        '\u{061C}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+        text,
+        expected_normalized_text,
+        case("cafe\u{301}", "café"),
+        case("Ｆｕｌｌ ｗｉｄｔｈ", "full width"),
+        case("zero\u{200B}width", "zerowidth"),
+        case("\u{202B}שלום\u{202C}", "שלום"),
+        case("ΟΔΟΣ ΣΟΦΙΑΣ", "οδος σοφιας"),
+        case("İstanbul", "istanbul")
+    )]
+    fn assert_text_is_normalized_correctly(text: &str, expected_normalized_text: &str) {
+        assert_eq!(normalize_text(text), expected_normalized_text);
+    }
+
+    #[test]
+    fn assert_turkish_capital_i_is_lowercased_independently_of_language() {
+        assert_eq!(normalize_text("KIRMIZI İNCİ"), "kirmizi inci");
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::sync::Arc;
+
+static EMAIL_ADDRESS: Lazy<Regex> =
+    Lazy::new(|| Regex::new("[\\w.%+-]+@[\\w-]+(?:\\.[\\w-]+)+").unwrap());
+static EMOJI_AND_SYMBOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new("[\\p{S}\\x{200D}\\x{FE0E}\\x{FE0F}]+").unwrap());
+static HASHTAG: Lazy<Regex> = Lazy::new(|| Regex::new("\\B#\\w+").unwrap());
+static INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new("(?s)```.*?```|`[^`\\n]*`").unwrap());
+static MENTION: Lazy<Regex> = Lazy::new(|| Regex::new("\\B@\\w+").unwrap());
+static URL: Lazy<Regex> =
+    Lazy::new(|| Regex::new("(?i)\\b(?:[a-z][a-z0-9+.-]*://|www\\.)\\S+").unwrap());
+
+/// This trait allows to remove or rewrite parts of the input text before its language is
+/// detected, such as URLs or email addresses which do not carry any evidence of the language.
+///
+/// Preprocessors are registered with
+/// [`LanguageDetectorBuilder::with_text_preprocessor`](./struct.LanguageDetectorBuilder.html#method.with_text_preprocessor)
+/// and applied in the order of registration.
+///
+/// ```
+/// use lingua::{LanguageDetectorBuilder, TextPreprocessor};
+///
+/// struct TicketNumberFilter;
+///
+/// impl TextPreprocessor for TicketNumberFilter {
+///     fn preprocess(&self, text: &str) -> String {
+///         text.split(' ')
+///             .filter(|word| !word.starts_with("TICKET-"))
+///             .collect::<Vec<_>>()
+///             .join(" ")
+///     }
+/// }
+///
+/// assert_eq!(TicketNumberFilter.preprocess("see TICKET-42 please"), "see please");
+/// assert_eq!(TicketNumberFilter.id(), None);
+///
+/// LanguageDetectorBuilder::from_all_languages()
+///     .with_default_text_preprocessors()
+///     .with_text_preprocessor(TicketNumberFilter);
+/// ```
+pub trait TextPreprocessor: Send + Sync {
+    /// Returns the preprocessed version of the given text.
+    fn preprocess(&self, text: &str) -> String;
+
+    /// Returns a name which identifies this preprocessor and its behavior across all
+    /// detectors sharing a result cache. Preprocessors without an id, which is the default,
+    /// disable the cache of the detectors they are registered with because their cached
+    /// confidence values could not be told apart from those of other preprocessors.
+    fn id(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Removes URLs such as `https://example.com/path` or `www.example.com`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UrlFilter;
+
+/// Removes email addresses such as `jane.doe@example.com`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmailAddressFilter;
+
+/// Removes mentions of users such as `@jane`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MentionFilter;
+
+/// Removes hashtags such as `#MondayMotivation`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashtagFilter;
+
+/// Removes emoji and other symbols, including emoji modifiers and joiners.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmojiFilter;
+
+/// Removes code snippets enclosed in backticks, both inline and fenced ones.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InlineCodeFilter;
+
+impl TextPreprocessor for UrlFilter {
+    fn preprocess(&self, text: &str) -> String {
+        URL.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("url")
+    }
+}
+
+impl TextPreprocessor for EmailAddressFilter {
+    fn preprocess(&self, text: &str) -> String {
+        EMAIL_ADDRESS.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("email_address")
+    }
+}
+
+impl TextPreprocessor for MentionFilter {
+    fn preprocess(&self, text: &str) -> String {
+        MENTION.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("mention")
+    }
+}
+
+impl TextPreprocessor for HashtagFilter {
+    fn preprocess(&self, text: &str) -> String {
+        HASHTAG.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("hashtag")
+    }
+}
+
+impl TextPreprocessor for EmojiFilter {
+    fn preprocess(&self, text: &str) -> String {
+        EMOJI_AND_SYMBOL.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("emoji")
+    }
+}
+
+impl TextPreprocessor for InlineCodeFilter {
+    fn preprocess(&self, text: &str) -> String {
+        INLINE_CODE.replace_all(text, " ").to_string()
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some("inline_code")
+    }
+}
+
+/// Returns the default chain of preprocessors. Code snippets are removed first because they
+/// may contain anything, URLs and email addresses before mentions because they contain
+/// an `@` as well.
+pub(crate) fn default_text_preprocessors() -> Vec<Arc<dyn TextPreprocessor>> {
+    vec![
+        Arc::new(InlineCodeFilter),
+        Arc::new(UrlFilter),
+        Arc::new(EmailAddressFilter),
+        Arc::new(MentionFilter),
+        Arc::new(HashtagFilter),
+        Arc::new(EmojiFilter),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::collections::HashSet;
+
+    fn preprocess_with_default_chain(text: &str) -> String {
+        default_text_preprocessors()
+            .iter()
+            .fold(text.to_string(), |text, preprocessor| {
+                preprocessor.preprocess(&text)
+            })
+    }
+
+    #[rstest(
+        text,
+        expected_text,
+        case("read https://example.com/a?b=c now", "read   now"),
+        case("visit www.example.com today", "visit   today"),
+        case("write to jane.doe@example.com soon", "write to   soon"),
+        case("thanks @jane for this", "thanks   for this"),
+        case("happy #MondayMotivation all", "happy   all"),
+        case("so good 👍🏽 really ❤️", "so good   really  "),
+        case("call `detect()` here", "call   here"),
+        case("code ```\nlet x = 1;\n``` there", "code   there")
+    )]
+    fn assert_default_chain_removes_noise(text: &str, expected_text: &str) {
+        assert_eq!(preprocess_with_default_chain(text), expected_text);
+    }
+
+    #[test]
+    fn assert_default_chain_has_distinct_ids() {
+        let ids = default_text_preprocessors()
+            .iter()
+            .filter_map(|preprocessor| preprocessor.id().map(|id| id.to_string()))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(ids.len(), 6);
+    }
+
+    #[test]
+    fn assert_email_address_is_not_treated_as_mention() {
+        assert_eq!(
+            preprocess_with_default_chain("mail jane@example.com"),
+            "mail  "
+        );
+    }
+}
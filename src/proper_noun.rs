@@ -0,0 +1,139 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use itertools::Itertools;
+
+/// The characters which end a sentence, so that the following word is sentence-initial.
+const SENTENCE_TERMINATORS: [char; 7] = ['.', '!', '?', '…', '。', '！', '？'];
+
+/// Finds the words of the given text which are most likely names of people, brands or places
+/// and therefore no evidence for the language of the text. These are acronyms written in
+/// uppercase letters only, CamelCase words, and capitalized words which do not start a sentence
+/// if they are unknown to all candidate languages or directly follow or precede another such
+/// capitalized word, as in *Washington Post*. This way, the capitalized common nouns of German
+/// are not mistaken for proper nouns. Whether a word is known is decided by `is_known_word`
+/// which receives the word as it occurs in the text.
+///
+/// The text must not have been lowercased yet. The words are returned in the order of their
+/// first occurrence, without surrounding punctuation. If every word of the text falls into one
+/// of the categories, as in a text written in uppercase letters only, none is returned.
+pub(crate) fn find_proper_nouns<F>(text: &str, is_known_word: F) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    // Each word is stored along with whether it starts a sentence
+    // and whether it directly follows the previous word without any punctuation in between.
+    let mut words = vec![];
+    let mut is_sentence_initial = true;
+    let mut is_attached = false;
+
+    for token in text.split_whitespace() {
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+
+        if word.chars().filter(|c| c.is_alphabetic()).count() >= 2 {
+            let is_attached_word = is_attached && token.starts_with(char::is_alphanumeric);
+            words.push((word, is_sentence_initial, is_attached_word));
+            is_attached = token.ends_with(char::is_alphanumeric);
+        } else {
+            is_attached = false;
+        }
+
+        if !word.is_empty() || token.ends_with(SENTENCE_TERMINATORS) {
+            is_sentence_initial = token.ends_with(SENTENCE_TERMINATORS);
+        }
+    }
+
+    let is_capitalized_within_sentence = |(word, is_sentence_initial, _): &(&str, bool, bool)| {
+        !is_sentence_initial && is_capitalized(word)
+    };
+    let is_in_capitalized_sequence = |i: usize| {
+        let follows_capitalized_word =
+            words[i].2 && i > 0 && is_capitalized_within_sentence(&words[i - 1]);
+        let precedes_capitalized_word = matches!(
+            words.get(i + 1),
+            Some(next) if next.2 && is_capitalized_within_sentence(next)
+        );
+        follows_capitalized_word || precedes_capitalized_word
+    };
+
+    let proper_nouns = words
+        .iter()
+        .enumerate()
+        .filter(|(i, entry)| {
+            let word = entry.0;
+            is_acronym(word)
+                || is_camel_case(word)
+                || (is_capitalized_within_sentence(entry)
+                    && (!is_known_word(word) || is_in_capitalized_sequence(*i)))
+        })
+        .map(|(_, (word, _, _))| word.to_string())
+        .collect_vec();
+
+    if proper_nouns.len() == words.len() {
+        return vec![];
+    }
+
+    proper_nouns.into_iter().unique().collect_vec()
+}
+
+fn is_acronym(word: &str) -> bool {
+    word.chars()
+        .filter(|c| c.is_alphabetic())
+        .all(|c| c.is_uppercase())
+}
+
+fn is_camel_case(word: &str) -> bool {
+    word.chars()
+        .tuple_windows()
+        .any(|(first, second)| first.is_lowercase() && second.is_uppercase())
+}
+
+fn is_capitalized(word: &str) -> bool {
+    word.starts_with(char::is_uppercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn is_known_german_word(word: &str) -> bool {
+        ["kurs", "montag", "post", "sonne"].contains(&word.to_lowercase().as_str())
+    }
+
+    #[rstest(
+        text,
+        expected_proper_nouns,
+        case(
+            "Das berichtet die Washington Post am Montag.",
+            vec!["Washington", "Post"]
+        ),
+        case("Der Kurs der NASA-Aktie steigt", vec!["NASA-Aktie"]),
+        case("Ich habe ein iPhone und nutze JavaScript", vec!["iPhone", "JavaScript"]),
+        case("Es regnet. Morgen scheint die Sonne! Wirklich?", vec![]),
+        case("Am Montag, Dienstag und Mittwoch", vec!["Dienstag", "Mittwoch"]),
+        case("« Paris » est belle", vec![]),
+        case("dans la ville de Paris et à Paris", vec!["Paris"]),
+        case("THIS IS SHOUTED", vec![]),
+        case("ich bin hier", vec![])
+    )]
+    fn assert_proper_nouns_are_found_correctly(text: &str, expected_proper_nouns: Vec<&str>) {
+        assert_eq!(
+            find_proper_nouns(text, is_known_german_word),
+            expected_proper_nouns
+        );
+    }
+}
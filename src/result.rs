@@ -0,0 +1,381 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::{Alphabet, ScriptVariant};
+use crate::language::Language;
+use itertools::Itertools;
+
+/// This struct describes a detected language in more detail, such as the script
+/// that the input text has been written in.
+///
+/// It is returned by [`LanguageDetector::detect_language_with_details_of`].
+///
+/// [`LanguageDetector::detect_language_with_details_of`]: ./struct.LanguageDetector.html#method.detect_language_with_details_of
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectionResult {
+    language: Language,
+    alphabet: Option<Alphabet>,
+    script_variant: Option<ScriptVariant>,
+    is_romanized: bool,
+    corrected_text: Option<String>,
+    has_normalized_homoglyphs: bool,
+    down_weighted_words: Vec<String>,
+    sample_agreement: Option<f64>,
+    is_partial: bool,
+}
+
+impl DetectionResult {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        language: Language,
+        alphabet: Option<Alphabet>,
+        script_variant: Option<ScriptVariant>,
+        is_romanized: bool,
+        corrected_text: Option<String>,
+        has_normalized_homoglyphs: bool,
+        down_weighted_words: Vec<String>,
+        sample_agreement: Option<f64>,
+        is_partial: bool,
+    ) -> Self {
+        Self {
+            language,
+            alphabet,
+            script_variant,
+            is_romanized,
+            corrected_text,
+            has_normalized_homoglyphs,
+            down_weighted_words,
+            sample_agreement,
+            is_partial,
+        }
+    }
+
+    /// Returns the detected language.
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// Returns the script that the input text has been written in
+    /// or `None` if it could not be determined.
+    pub fn alphabet(&self) -> Option<&Alphabet> {
+        self.alphabet.as_ref()
+    }
+
+    /// Returns the variant of the script that the input text has been written in, such as
+    /// Simplified or Traditional Han characters for Chinese, or `None` if the language does not
+    /// distinguish script variants or if the variant could not be determined.
+    pub fn script_variant(&self) -> Option<&ScriptVariant> {
+        self.script_variant.as_ref()
+    }
+
+    /// Returns `true` if the input text has been written in Latin script
+    /// although the detected language is natively written in a different one,
+    /// such as Russian in *privet kak dela*.
+    pub fn is_romanized(&self) -> bool {
+        self.is_romanized
+    }
+
+    /// Returns the input text as it was meant to be typed if it has been typed with the
+    /// wrong keyboard layout, such as *привет* for *ghbdtn*, or `None` otherwise.
+    pub fn corrected_text(&self) -> Option<&str> {
+        self.corrected_text.as_deref()
+    }
+
+    /// Returns `true` if look-alike letters of a different script, such as the
+    /// Cyrillic *а* in *pаypаl*, have been replaced before detecting the language.
+    pub fn has_normalized_homoglyphs(&self) -> bool {
+        self.has_normalized_homoglyphs
+    }
+
+    /// Returns the words of the input text which have contributed less to the detection
+    /// because they are most likely proper nouns, such as *Washington* and *Post* in
+    /// *Das berichtet die Washington Post*. The vector is empty unless the down-weighting
+    /// of proper nouns has been enabled.
+    pub fn down_weighted_words(&self) -> &[String] {
+        &self.down_weighted_words
+    }
+
+    /// Returns the share of the windows of the input text's sample, between 0.0 and 1.0,
+    /// whose language equals the detected language when each window is detected separately.
+    /// Values below 1.0 indicate that the sample disagrees with itself, for instance because
+    /// the input text is written in several languages. `None` is returned unless the
+    /// consistency check has been enabled for a sampling strategy with several windows.
+    pub fn sample_agreement(&self) -> Option<f64> {
+        self.sample_agreement
+    }
+
+    /// Returns `true` if the time budget of
+    /// [`LanguageDetector::detect_language_of_within`](./struct.LanguageDetector.html#method.detect_language_of_within)
+    /// has been used up before all ngram orders could be scored, so that the language
+    /// has been detected from the lower-order ngrams only.
+    pub fn is_partial(&self) -> bool {
+        self.is_partial
+    }
+
+    /// Returns a language tag such as `sr-Latn`, `sr-Cyrl` or `zh-Hant`, composed of the
+    /// language's ISO 639-1 code and the ISO 15924 code of the script or its variant.
+    /// If the script could not be determined, only the ISO 639-1 code is returned.
+    pub fn language_tag(&self) -> String {
+        let iso_code = self.language.iso_code_639_1();
+
+        match (self.script_variant, self.alphabet) {
+            (Some(script_variant), _) => {
+                format!("{}-{}", iso_code, script_variant.iso_code_15924())
+            }
+            (None, Some(alphabet)) => format!("{}-{}", iso_code, alphabet.iso_code_15924()),
+            (None, None) => iso_code.to_string(),
+        }
+    }
+}
+
+/// This struct describes the detected language of a document which consists of several
+/// fields, together with the languages detected for each of them.
+///
+/// It is returned by [`LanguageDetector::detect_language_of_document`].
+///
+/// [`LanguageDetector::detect_language_of_document`]: ./struct.LanguageDetector.html#method.detect_language_of_document
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentDetectionResult {
+    language: Option<Language>,
+    confidence_values: Vec<(Language, f64)>,
+    field_languages: Vec<Option<Language>>,
+    field_confidence_values: Vec<Vec<(Language, f64)>>,
+}
+
+impl DocumentDetectionResult {
+    pub(crate) fn new(
+        language: Option<Language>,
+        confidence_values: Vec<(Language, f64)>,
+        field_languages: Vec<Option<Language>>,
+        field_confidence_values: Vec<Vec<(Language, f64)>>,
+    ) -> Self {
+        Self {
+            language,
+            confidence_values,
+            field_languages,
+            field_confidence_values,
+        }
+    }
+
+    /// Returns the language of the whole document or `None`
+    /// if it cannot be reliably detected.
+    pub fn language(&self) -> Option<&Language> {
+        self.language.as_ref()
+    }
+
+    /// Returns the confidence values of the whole document, sorted in descending order.
+    /// The most likely language has the value 1.0, all other languages have lower values.
+    pub fn confidence_values(&self) -> &[(Language, f64)] {
+        &self.confidence_values
+    }
+
+    /// Returns the language detected for each field in the order in which the fields
+    /// have been given, or `None` for fields whose language cannot be reliably detected.
+    pub fn field_languages(&self) -> &[Option<Language>] {
+        &self.field_languages
+    }
+
+    /// Returns the confidence values computed for each field in the order
+    /// in which the fields have been given.
+    pub fn field_confidence_values(&self) -> &[Vec<(Language, f64)>] {
+        &self.field_confidence_values
+    }
+
+    /// Returns `true` if the languages of at least two fields have been detected
+    /// and differ from each other, such as an English title above a German body.
+    pub fn has_disagreeing_fields(&self) -> bool {
+        self.field_languages
+            .iter()
+            .flatten()
+            .tuple_windows()
+            .any(|(first, second)| first != second)
+    }
+}
+
+/// This struct describes whether text has been verified to be written in an expected language.
+///
+/// It is returned by [`LanguageDetector::verify_language`].
+///
+/// [`LanguageDetector::verify_language`]: ./struct.LanguageDetector.html#method.verify_language
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    language: Language,
+    score: f64,
+    margin: Option<f64>,
+    strongest_competitor: Option<Language>,
+    is_ruled_out_by_rules: bool,
+}
+
+impl Verification {
+    pub(crate) fn new(
+        language: Language,
+        score: f64,
+        margin: Option<f64>,
+        strongest_competitor: Option<Language>,
+        is_ruled_out_by_rules: bool,
+    ) -> Self {
+        Self {
+            language,
+            score,
+            margin,
+            strongest_competitor,
+            is_ruled_out_by_rules,
+        }
+    }
+
+    /// Returns the language which the text has been verified against.
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// Returns `true` if the text is more likely to be written in the expected language
+    /// than in any other language of the detector.
+    pub fn is_verified(&self) -> bool {
+        self.score > 0.5
+    }
+
+    /// Returns the estimated probability that the text is written in the expected language
+    /// rather than in its strongest competitor. It has been calibrated on held-out test data
+    /// of all languages, where the scores differ from the observed share of texts written in
+    /// the expected language by 0.04 on average.
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Returns the lead of the expected language over its strongest competitor from which
+    /// the score is computed, that is the difference of their logarithmized likelihoods scaled
+    /// by the square root of the number of letters in the text. It is negative if the
+    /// competitor is more likely. `None` is returned if the ngram models have not been
+    /// consulted or if there is no competing language.
+    pub fn margin(&self) -> Option<f64> {
+        self.margin
+    }
+
+    /// Returns the most likely language other than the expected one or `None` if there is no
+    /// competing language, for instance because the rule-based engine has ruled out the
+    /// expected language without deciding on another one.
+    pub fn strongest_competitor(&self) -> Option<&Language> {
+        self.strongest_competitor.as_ref()
+    }
+
+    /// Returns `true` if the rule-based engine has ruled out the expected language because of
+    /// the text's script alone, without consulting the ngram models.
+    pub fn is_ruled_out_by_rules(&self) -> bool {
+        self.is_ruled_out_by_rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn assert_language_tag_contains_script() {
+        let result = DetectionResult::new(
+            Language::Serbian,
+            Some(Alphabet::Latin),
+            None,
+            false,
+            None,
+            false,
+            vec![],
+            None,
+            false,
+        );
+        assert_eq!(result.language_tag(), "sr-Latn");
+
+        let result = DetectionResult::new(
+            Language::Serbian,
+            Some(Alphabet::Cyrillic),
+            None,
+            false,
+            None,
+            false,
+            vec![],
+            None,
+            false,
+        );
+        assert_eq!(result.language_tag(), "sr-Cyrl");
+    }
+
+    #[test]
+    fn assert_language_tag_contains_script_variant() {
+        let result = DetectionResult::new(
+            Language::Chinese,
+            Some(Alphabet::Han),
+            Some(ScriptVariant::Traditional),
+            false,
+            None,
+            false,
+            vec![],
+            None,
+            false,
+        );
+        assert_eq!(result.language_tag(), "zh-Hant");
+    }
+
+    #[test]
+    fn assert_language_tag_of_romanized_language_contains_latin_script() {
+        let result = DetectionResult::new(
+            Language::Russian,
+            Some(Alphabet::Latin),
+            None,
+            true,
+            None,
+            false,
+            vec![],
+            None,
+            false,
+        );
+        assert!(result.is_romanized());
+        assert_eq!(result.language_tag(), "ru-Latn");
+    }
+
+    #[test]
+    fn assert_language_tag_without_script() {
+        let result = DetectionResult::new(
+            Language::English,
+            None,
+            None,
+            false,
+            None,
+            false,
+            vec![],
+            None,
+            false,
+        );
+        assert_eq!(result.language_tag(), "en");
+    }
+
+    #[rstest(
+        field_languages,
+        expected_disagreement,
+        case(vec![], false),
+        case(vec![Some(Language::German), None, Some(Language::German)], false),
+        case(vec![Some(Language::German), None, Some(Language::English)], true),
+        case(vec![None, None], false)
+    )]
+    fn assert_disagreeing_fields_are_recognized(
+        field_languages: Vec<Option<Language>>,
+        expected_disagreement: bool,
+    ) {
+        let field_confidence_values = vec![vec![]; field_languages.len()];
+        let result =
+            DocumentDetectionResult::new(None, vec![], field_languages, field_confidence_values);
+        assert_eq!(result.has_disagreeing_fields(), expected_disagreement);
+    }
+}
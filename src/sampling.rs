@@ -0,0 +1,196 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::iter::once;
+
+/// This enum specifies how a representative sample is taken from long input texts
+/// in order to detect their language faster.
+///
+/// All lengths are measured in characters. Windows are shortened to whole words
+/// where possible. Texts which are not longer than the sample are not shortened.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SamplingStrategy {
+    /// Takes the given number of characters from the beginning of the text.
+    FirstCharacters(usize),
+
+    /// Takes the given number of windows which are spaced evenly across the text,
+    /// starting at its beginning and ending at its end.
+    EvenlySpacedWindows {
+        window_count: usize,
+        window_length: usize,
+    },
+
+    /// Takes the given number of windows at random positions of the text. The positions
+    /// are derived from the given seed so that the detection stays reproducible.
+    RandomWindows {
+        window_count: usize,
+        window_length: usize,
+        seed: u64,
+    },
+}
+
+impl SamplingStrategy {
+    /// Returns the windows of the given text which make up its sample, in text order.
+    pub(crate) fn sample<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let (window_count, window_length) = self.windows();
+        let char_count = text.chars().count();
+
+        if window_count.saturating_mul(window_length) >= char_count {
+            return vec![text];
+        }
+
+        let last_start = char_count - window_length;
+        let mut starts = match *self {
+            SamplingStrategy::FirstCharacters(_) => vec![0],
+            SamplingStrategy::EvenlySpacedWindows { .. } => (0..window_count)
+                .map(|i| i * last_start / (window_count - 1).max(1))
+                .collect(),
+            SamplingStrategy::RandomWindows { seed, .. } => {
+                let mut generator = SplitMix64(seed);
+                (0..window_count)
+                    .map(|_| (generator.next() % (last_start as u64 + 1)) as usize)
+                    .collect()
+            }
+        };
+        starts.sort_unstable();
+
+        starts
+            .into_iter()
+            .map(|start| extract_window(text, start, window_length))
+            .filter(|window| !window.is_empty())
+            .collect()
+    }
+
+    /// Returns the number of windows and the length of each window in characters.
+    pub(crate) fn windows(&self) -> (usize, usize) {
+        match *self {
+            SamplingStrategy::FirstCharacters(length) => (1, length),
+            SamplingStrategy::EvenlySpacedWindows {
+                window_count,
+                window_length,
+            } => (window_count, window_length),
+            SamplingStrategy::RandomWindows {
+                window_count,
+                window_length,
+                ..
+            } => (window_count, window_length),
+        }
+    }
+}
+
+/// Returns the given number of characters of the given text from the given character
+/// position on, without the words which are cut off at the window's borders.
+fn extract_window(text: &str, start: usize, length: usize) -> &str {
+    if length == 0 {
+        return "";
+    }
+
+    let mut byte_offsets = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(once(text.len()));
+    let start_offset = byte_offsets.nth(start).unwrap_or(text.len());
+    let end_offset = byte_offsets.nth(length - 1).unwrap_or(text.len());
+    let mut window = &text[start_offset..end_offset];
+
+    if start_offset > 0 {
+        if let Some(offset) = window.find(char::is_whitespace) {
+            window = &window[offset..];
+        }
+    }
+
+    if end_offset < text.len() {
+        if let Some(offset) = window.rfind(char::is_whitespace) {
+            window = &window[..offset];
+        }
+    }
+
+    window.trim()
+}
+
+/// A SplitMix64 generator, which suffices to pick reproducible window positions.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const TEXT: &str = "one two three four five six seven eight nine ten";
+
+    #[rstest(
+        strategy,
+        expected_sample,
+        case(SamplingStrategy::FirstCharacters(100), vec![TEXT]),
+        case(SamplingStrategy::FirstCharacters(15), vec!["one two three"]),
+        case(
+            SamplingStrategy::EvenlySpacedWindows {
+                window_count: 3,
+                window_length: 12
+            },
+            vec!["one two", "five six", "nine ten"]
+        ),
+        case(
+            SamplingStrategy::EvenlySpacedWindows {
+                window_count: 5,
+                window_length: 10
+            },
+            vec![TEXT]
+        ),
+        case(
+            SamplingStrategy::EvenlySpacedWindows {
+                window_count: usize::MAX,
+                window_length: usize::MAX
+            },
+            vec![TEXT]
+        )
+    )]
+    fn assert_text_is_sampled_correctly(strategy: SamplingStrategy, expected_sample: Vec<&str>) {
+        assert_eq!(strategy.sample(TEXT), expected_sample);
+    }
+
+    #[test]
+    fn assert_random_windows_are_reproducible() {
+        let strategy = SamplingStrategy::RandomWindows {
+            window_count: 2,
+            window_length: 12,
+            seed: 42,
+        };
+        let sample = strategy.sample(TEXT);
+
+        assert_eq!(sample.len(), 2);
+        assert!(sample.iter().all(|window| TEXT.contains(window)));
+        assert_eq!(strategy.sample(TEXT), sample);
+    }
+
+    #[test]
+    fn assert_windows_respect_character_boundaries() {
+        assert_eq!(
+            SamplingStrategy::FirstCharacters(9).sample("ภาษาไทย ภาษาไทย"),
+            vec!["ภาษาไทย"]
+        );
+    }
+}
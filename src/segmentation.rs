@@ -0,0 +1,123 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The kinds of text runs which are split into words in different ways.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ScriptRun {
+    /// Han and Kana characters, which are written without spaces.
+    Cjk,
+    /// Thai characters, which are written without spaces between words.
+    Thai,
+    /// Characters of all other scripts, which separate words by spaces.
+    Other,
+}
+
+/// Splits the given text into words, taking into account that some scripts do not
+/// separate their words by spaces.
+///
+/// Runs of Han characters without any Kana are split into single characters, as each
+/// of them mostly forms a word of its own in Chinese. Runs which contain Kana are kept
+/// together as they belong to Japanese text. Runs of Thai characters are kept together
+/// as well. All other text is split at its Unicode word boundaries.
+pub(crate) fn split_into_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+
+    for (script_run, run) in split_into_script_runs(text) {
+        match script_run {
+            ScriptRun::Cjk if !run.chars().any(is_kana) => {
+                words.extend(run.char_indices().map(|(i, c)| &run[i..i + c.len_utf8()]))
+            }
+            ScriptRun::Cjk | ScriptRun::Thai => words.push(run),
+            ScriptRun::Other => words.extend(run.unicode_words()),
+        }
+    }
+
+    words
+}
+
+fn split_into_script_runs(text: &str) -> Vec<(ScriptRun, &str)> {
+    let mut runs = vec![];
+    let mut start = 0;
+    let mut current_run = None;
+
+    for (i, character) in text.char_indices() {
+        let script_run = classify_character(character);
+
+        match current_run {
+            Some(run) if run != script_run => {
+                runs.push((run, &text[start..i]));
+                start = i;
+            }
+            _ => {}
+        }
+
+        current_run = Some(script_run);
+    }
+
+    if let Some(run) = current_run {
+        runs.push((run, &text[start..]));
+    }
+
+    runs
+}
+
+fn classify_character(character: char) -> ScriptRun {
+    let mut buffer = [0; 4];
+    let char_str = character.encode_utf8(&mut buffer);
+
+    if is_kana(character) || Alphabet::Han.matches(char_str) {
+        ScriptRun::Cjk
+    } else if Alphabet::Thai.matches(char_str) {
+        ScriptRun::Thai
+    } else {
+        ScriptRun::Other
+    }
+}
+
+fn is_kana(character: char) -> bool {
+    let mut buffer = [0; 4];
+    let char_str = character.encode_utf8(&mut buffer);
+
+    // the prolonged sound mark belongs to the common script
+    character == 'ー'
+        || Alphabet::Hiragana.matches(char_str)
+        || Alphabet::Katakana.matches(char_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+        text,
+        expected_words,
+        case("this is a sentence", vec!["this", "is", "a", "sentence"]),
+        case("sentence", vec!["sentence"]),
+        case("l'homme est bien-aimé", vec!["l'homme", "est", "bien", "aimé"]),
+        case("我爱你 hello", vec!["我", "爱", "你", "hello"]),
+        case("iphone很好", vec!["iphone", "很", "好"]),
+        case("東京に行きます", vec!["東京に行きます"]),
+        case("コーヒーをください", vec!["コーヒーをください"]),
+        case("ภาษาไทย ok", vec!["ภาษาไทย", "ok"])
+    )]
+    fn assert_text_is_split_into_words_correctly(text: &str, expected_words: Vec<&str>) {
+        assert_eq!(split_into_words(text), expected_words);
+    }
+}
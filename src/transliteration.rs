@@ -0,0 +1,363 @@
+/*
+ * Copyright © 2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use crate::constant::FINAL_SIGMA;
+use crate::language::Language;
+use crate::language::Language::*;
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+type TransliterationTable = Vec<(&'static str, &'static str)>;
+
+/// Maps a language and a script that it is written in to the replacements which convert
+/// lowercase text of that script into the script that the language models have been trained on.
+///
+/// Languages written in several scripts, such as Serbian or Azerbaijani, do not come with a
+/// separate model set for each script because training data is only available in one of them.
+/// Text in any other script is scored by the models of the trained script after transliteration.
+/// As both scripts of these languages map onto each other almost letter by letter, the
+/// transliterated text yields the same ngrams as native text would. This is less accurate than
+/// dedicated models only where the scripts are not mapped one to one, for instance for the
+/// Azerbaijani Cyrillic *щ* which is rendered as *şç*. Serbian in Latin script is additionally
+/// restricted by [`NATIVE_SCRIPT_RIVALS`], so that the accuracy of Bosnian, Croatian and
+/// Slovene is unaffected by it.
+static TRANSLITERATION_TABLES: Lazy<HashMap<(Language, Alphabet), TransliterationTable>> =
+    Lazy::new(|| {
+        hashmap!(
+            (Azerbaijani, Alphabet::Cyrillic) => create_table(hashmap!(
+                "а" => "a", "б" => "b", "в" => "v", "г" => "q", "ғ" => "ğ", "д" => "d",
+                "е" => "e", "ә" => "ə", "ж" => "j", "з" => "z", "и" => "i", "ы" => "ı",
+                "ј" => "y", "й" => "y", "к" => "k", "ҝ" => "g", "л" => "l", "м" => "m",
+                "н" => "n", "о" => "o", "ө" => "ö", "п" => "p", "р" => "r", "с" => "s",
+                "т" => "t", "у" => "u", "ү" => "ü", "ф" => "f", "х" => "x", "һ" => "h",
+                "ч" => "ç", "ҹ" => "c", "ш" => "ş", "ц" => "ts", "щ" => "şç", "э" => "e",
+                "ю" => "yu", "я" => "ya", "ё" => "yo", "ъ" => "", "ь" => ""
+            )),
+            (Serbian, Alphabet::Latin) => create_table(hashmap!(
+                "a" => "а", "b" => "б", "c" => "ц", "č" => "ч", "ć" => "ћ", "d" => "д",
+                "dž" => "џ", "đ" => "ђ", "e" => "е", "f" => "ф", "g" => "г", "h" => "х",
+                "i" => "и", "j" => "ј", "k" => "к", "l" => "л", "lj" => "љ", "m" => "м",
+                "n" => "н", "nj" => "њ", "o" => "о", "p" => "п", "r" => "р", "s" => "с",
+                "š" => "ш", "t" => "т", "u" => "у", "v" => "в", "z" => "з", "ž" => "ж"
+            ))
+        )
+    });
+
+/// Maps a language and a script that it is transliterated from to the languages whose models
+/// have been trained on that script and which are too close to it to be told apart reliably.
+///
+/// Serbian written in Latin script is largely indistinguishable from Croatian and Bosnian,
+/// so that its Cyrillic models, applied to transliterated text, would claim a considerable
+/// share of Croatian and Bosnian texts. It is therefore only considered for Latin text if
+/// neither of them is among the languages to detect. Azerbaijani written in Cyrillic script
+/// has no such close relative, so its transliterated text competes with all other languages.
+static NATIVE_SCRIPT_RIVALS: Lazy<HashMap<(Language, Alphabet), Vec<Language>>> = Lazy::new(|| {
+    hashmap!(
+        (Serbian, Alphabet::Latin) => vec![Bosnian, Croatian]
+    )
+});
+
+/// Maps languages which are not written in Latin script to the replacements which convert
+/// their informal romanizations, as commonly typed on the web, back into their native script.
+static ROMANIZATION_TABLES: Lazy<HashMap<Language, TransliterationTable>> = Lazy::new(|| {
+    hashmap!(
+        Bulgarian => create_table(hashmap!(
+            "a" => "а", "b" => "б", "v" => "в", "g" => "г", "d" => "д", "e" => "е",
+            "zh" => "ж", "z" => "з", "i" => "и", "y" => "й", "j" => "й", "k" => "к",
+            "l" => "л", "m" => "м", "n" => "н", "o" => "о", "p" => "п", "r" => "р",
+            "s" => "с", "t" => "т", "u" => "у", "f" => "ф", "h" => "х", "ts" => "ц",
+            "ch" => "ч", "sh" => "ш", "sht" => "щ", "yu" => "ю", "ya" => "я", "c" => "ц",
+            "w" => "в", "x" => "кс", "q" => "к"
+        )),
+        Greek => create_table(hashmap!(
+            "a" => "α", "b" => "μπ", "v" => "β", "g" => "γ", "d" => "δ", "e" => "ε",
+            "z" => "ζ", "i" => "ι", "th" => "θ", "k" => "κ", "l" => "λ", "m" => "μ",
+            "n" => "ν", "x" => "ξ", "ks" => "ξ", "o" => "ο", "p" => "π", "r" => "ρ",
+            "s" => "σ", "t" => "τ", "y" => "υ", "f" => "φ", "ph" => "φ", "ch" => "χ",
+            "h" => "χ", "ps" => "ψ", "w" => "ω", "ou" => "ου", "u" => "ου", "c" => "κ",
+            "j" => "τζ", "q" => "κ"
+        )),
+        Russian => create_table(hashmap!(
+            "a" => "а", "b" => "б", "v" => "в", "g" => "г", "d" => "д", "e" => "е",
+            "yo" => "ё", "zh" => "ж", "z" => "з", "i" => "и", "j" => "й", "k" => "к",
+            "l" => "л", "m" => "м", "n" => "н", "o" => "о", "p" => "п", "r" => "р",
+            "s" => "с", "t" => "т", "u" => "у", "f" => "ф", "h" => "х", "kh" => "х",
+            "ts" => "ц", "c" => "ц", "ch" => "ч", "sh" => "ш", "sch" => "щ", "shch" => "щ",
+            "y" => "ы", "ay" => "ай", "ey" => "ей", "iy" => "ий", "oy" => "ой",
+            "uy" => "уй", "yu" => "ю", "ya" => "я", "w" => "в", "x" => "кс", "q" => "к"
+        )),
+        Ukrainian => create_table(hashmap!(
+            "a" => "а", "b" => "б", "v" => "в", "h" => "г", "g" => "ґ", "d" => "д",
+            "e" => "е", "ye" => "є", "zh" => "ж", "z" => "з", "y" => "и", "i" => "і",
+            "yi" => "ї", "j" => "й", "k" => "к", "l" => "л", "m" => "м", "n" => "н",
+            "o" => "о", "p" => "п", "r" => "р", "s" => "с", "t" => "т", "u" => "у",
+            "f" => "ф", "kh" => "х", "ts" => "ц", "c" => "ц", "ch" => "ч", "sh" => "ш",
+            "shch" => "щ", "yu" => "ю", "ya" => "я", "w" => "в", "x" => "кс", "q" => "к"
+        ))
+    )
+});
+
+/// Maps the Latin spellings of Devanagari consonants to their native characters.
+static DEVANAGARI_CONSONANTS: Lazy<TransliterationTable> = Lazy::new(|| {
+    create_table(hashmap!(
+        "k" => "क", "kh" => "ख", "g" => "ग", "gh" => "घ", "ch" => "च", "chh" => "छ",
+        "j" => "ज", "jh" => "झ", "t" => "त", "th" => "थ", "d" => "द", "dh" => "ध",
+        "n" => "न", "p" => "प", "ph" => "फ", "f" => "फ", "b" => "ब", "bh" => "भ",
+        "m" => "म", "y" => "य", "r" => "र", "l" => "ल", "v" => "व", "w" => "व",
+        "sh" => "श", "s" => "स", "h" => "ह", "z" => "ज", "q" => "क", "c" => "क",
+        "x" => "क्स"
+    ))
+});
+
+/// Maps the Latin spellings of Devanagari vowels to their independent letters
+/// and to the dependent signs which are used after a consonant.
+static DEVANAGARI_VOWELS: Lazy<Vec<(&'static str, (&'static str, &'static str))>> =
+    Lazy::new(|| {
+        hashmap!(
+            "a" => ("अ", ""), "aa" => ("आ", "ा"), "i" => ("इ", "ि"), "ee" => ("ई", "ी"),
+            "ii" => ("ई", "ी"), "u" => ("उ", "ु"), "oo" => ("ऊ", "ू"), "uu" => ("ऊ", "ू"),
+            "e" => ("ए", "े"), "ai" => ("ऐ", "ै"), "o" => ("ओ", "ो"), "au" => ("औ", "ौ")
+        )
+        .into_iter()
+        .sorted_by(|(first, _), (second, _)| compare_by_length(first, second))
+        .collect_vec()
+    });
+
+/// Converts the given text, written in the given script, into the script
+/// that the models of the given language have been trained on.
+///
+/// Returns `None` if the language's models already cover the script
+/// or if no transliteration is known for it.
+pub(crate) fn transliterate(
+    text: &str,
+    language: &Language,
+    alphabet: &Alphabet,
+) -> Option<String> {
+    let table = TRANSLITERATION_TABLES.get(&(language.clone(), *alphabet))?;
+    Some(replace_greedily(text, table))
+}
+
+/// Returns `true` if text in the given script is to be left to the models of another of the
+/// given languages, trained on that script, instead of being transliterated for the given one.
+pub(crate) fn is_outranked_by_native_script_rivals(
+    language: &Language,
+    alphabet: &Alphabet,
+    languages: &HashSet<Language>,
+) -> bool {
+    match NATIVE_SCRIPT_RIVALS.get(&(language.clone(), *alphabet)) {
+        Some(rivals) => rivals.iter().any(|rival| languages.contains(rival)),
+        None => false,
+    }
+}
+
+/// Returns `true` if informal romanizations of the given language
+/// can be converted back into its native script.
+pub(crate) fn is_romanizable(language: &Language) -> bool {
+    matches!(language, Hindi | Marathi) || ROMANIZATION_TABLES.contains_key(language)
+}
+
+/// Converts the given text, written in Latin script, into the native script of the given
+/// language, assuming that it is an informal romanization of that language.
+///
+/// Returns `None` if no romanization is known for the language.
+pub(crate) fn deromanize(text: &str, language: &Language) -> Option<String> {
+    match language {
+        Hindi | Marathi => Some(deromanize_devanagari(text)),
+        Greek => {
+            let result = replace_greedily(text, ROMANIZATION_TABLES.get(language)?);
+            Some(FINAL_SIGMA.replace_all(&result, "ς").to_string())
+        }
+        _ => Some(replace_greedily(text, ROMANIZATION_TABLES.get(language)?)),
+    }
+}
+
+/// Spells romanized words in Devanagari, taking into account that consonants carry an
+/// inherent vowel and are joined by a virama if no vowel follows. A trailing *a* is read
+/// as a long vowel, as in *kya* or *mera*, a trailing consonant as being without vowel.
+fn deromanize_devanagari(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() * 3);
+    let mut remainder = text;
+    let mut follows_consonant = false;
+
+    while let Some(character) = remainder.chars().next() {
+        if let Some((source, consonant)) = DEVANAGARI_CONSONANTS
+            .iter()
+            .find(|(source, _)| remainder.starts_with(source))
+        {
+            if follows_consonant {
+                result.push('्');
+            }
+            result.push_str(consonant);
+            remainder = &remainder[source.len()..];
+            follows_consonant = true;
+        } else if let Some((source, (letter, sign))) = DEVANAGARI_VOWELS
+            .iter()
+            .find(|(source, _)| remainder.starts_with(source))
+        {
+            remainder = &remainder[source.len()..];
+            let is_word_end = !remainder.starts_with(char::is_alphabetic);
+
+            if !follows_consonant {
+                result.push_str(letter);
+            } else if *source == "a" && is_word_end {
+                result.push('ा');
+            } else {
+                result.push_str(sign);
+            }
+            follows_consonant = false;
+        } else {
+            result.push(character);
+            remainder = &remainder[character.len_utf8()..];
+            follows_consonant = false;
+        }
+    }
+
+    result
+}
+
+fn replace_greedily(text: &str, table: &[(&'static str, &'static str)]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut remainder = text;
+
+    while let Some(character) = remainder.chars().next() {
+        match table
+            .iter()
+            .find(|(source, _)| remainder.starts_with(source))
+        {
+            Some((source, target)) => {
+                result.push_str(target);
+                remainder = &remainder[source.len()..];
+            }
+            None => {
+                result.push(character);
+                remainder = &remainder[character.len_utf8()..];
+            }
+        }
+    }
+
+    result
+}
+
+fn create_table(mapping: HashMap<&'static str, &'static str>) -> TransliterationTable {
+    mapping
+        .into_iter()
+        .sorted_by(|(first, _), (second, _)| compare_by_length(first, second))
+        .collect_vec()
+}
+
+/// Sorts longer sources first so that digraphs take precedence over single letters.
+fn compare_by_length(first: &str, second: &str) -> Ordering {
+    second
+        .chars()
+        .count()
+        .cmp(&first.chars().count())
+        .then(first.cmp(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_serbian_latin_is_transliterated_into_cyrillic() {
+        assert_eq!(
+            transliterate("ljubav i džem nije ćorsokak", &Serbian, &Alphabet::Latin),
+            Some("љубав и џем није ћорсокак".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_azerbaijani_cyrillic_is_transliterated_into_latin() {
+        assert_eq!(
+            transliterate("азәрбајҹан дили", &Azerbaijani, &Alphabet::Cyrillic),
+            Some("azərbaycan dili".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_romanized_cyrillic_is_converted_into_native_script() {
+        assert_eq!(
+            deromanize("privet kak dela", &Russian),
+            Some("привет как дела".to_string())
+        );
+        assert_eq!(
+            deromanize("dyakuyu za dopomohu", &Ukrainian),
+            Some("дякую за допомогу".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_romanized_greek_is_converted_into_native_script() {
+        assert_eq!(
+            deromanize("kalos orises", &Greek),
+            Some("καλος ορισες".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_romanized_hindi_is_converted_into_native_script() {
+        assert_eq!(
+            deromanize("aap kaise ho kya naam hai mera", &Hindi),
+            Some("आप कैसे हो क्या नाम है मेरा".to_string())
+        );
+    }
+
+    #[test]
+    fn assert_languages_without_romanization_are_not_converted() {
+        assert!(is_romanizable(&Russian));
+        assert!(is_romanizable(&Hindi));
+        assert!(!is_romanizable(&English));
+        assert_eq!(deromanize("house", &English), None);
+    }
+
+    #[test]
+    fn assert_text_in_model_script_is_not_transliterated() {
+        assert_eq!(transliterate("љубав", &Serbian, &Alphabet::Cyrillic), None);
+        assert_eq!(transliterate("house", &English, &Alphabet::Latin), None);
+    }
+
+    #[test]
+    fn assert_serbian_latin_is_outranked_by_croatian_and_bosnian() {
+        assert!(is_outranked_by_native_script_rivals(
+            &Serbian,
+            &Alphabet::Latin,
+            &hashset!(Croatian, English, Serbian)
+        ));
+        assert!(is_outranked_by_native_script_rivals(
+            &Serbian,
+            &Alphabet::Latin,
+            &hashset!(Bosnian, Serbian)
+        ));
+        assert!(!is_outranked_by_native_script_rivals(
+            &Serbian,
+            &Alphabet::Latin,
+            &hashset!(English, Serbian, Slovene)
+        ));
+        assert!(!is_outranked_by_native_script_rivals(
+            &Serbian,
+            &Alphabet::Cyrillic,
+            &hashset!(Bosnian, Croatian, Serbian)
+        ));
+        assert!(!is_outranked_by_native_script_rivals(
+            &Azerbaijani,
+            &Alphabet::Cyrillic,
+            &hashset!(Azerbaijani, Kazakh, Russian)
+        ));
+    }
+}
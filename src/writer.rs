@@ -15,8 +15,9 @@
  */
 
 use crate::constant::{MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION};
-use crate::model::TrainingDataLanguageModel;
+use crate::model::{PairwiseLanguageModel, TrainingDataLanguageModel, WordLanguageModel};
 use crate::ngram::Ngram;
+use crate::normalization::normalize_text;
 use crate::Language;
 use itertools::Itertools;
 use regex::Regex;
@@ -127,6 +128,119 @@ impl LanguageModelFilesWriter {
         Ok(())
     }
 
+    /// Creates a word model file for the detection of short texts and writes it to a directory.
+    ///
+    /// The word model contains the relative frequencies of the most common words of the language,
+    /// measured against all words of the input file. It is written as `words.json.zip` and can be
+    /// placed next to the ngram model files of the respective language model crate.
+    ///
+    /// `input_file_path`: The path to a txt file used for word model creation.
+    /// The assumed encoding of the txt file is UTF-8.
+    ///
+    /// `output_directory_path`: The path to an existing directory where the word model file
+    /// is to be written.
+    ///
+    /// `language`: The language for which to create the word model.
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the words are built from.
+    ///
+    /// `maximum_words`: The maximum number of most common words the word model should contain.
+    ///
+    /// ⚠ Panics if:
+    /// - the input file path is not absolute or does not point to an existing txt file
+    /// - the input file's encoding is not UTF-8
+    /// - the output directory path is not absolute or does not point to an existing directory
+    /// - the character class cannot be compiled to a valid regular expression
+    pub fn create_and_write_word_model_file(
+        input_file_path: &Path,
+        output_directory_path: &Path,
+        language: &Language,
+        char_class: &str,
+        maximum_words: u32,
+    ) -> io::Result<()> {
+        check_input_file_path(input_file_path);
+        check_output_directory_path(output_directory_path);
+
+        let lines = read_non_empty_lines(input_file_path)?;
+        let lines_as_str = lines.iter().map(|line| line.as_str()).collect_vec();
+        let word_model = WordLanguageModel::from_text(
+            &lines_as_str,
+            language,
+            char_class,
+            maximum_words as usize,
+        );
+
+        write_compressed_json(&word_model.to_json(), output_directory_path, "words.json")
+    }
+
+    /// Creates a pairwise model file for two easily confused languages and writes it to a directory.
+    ///
+    /// The pairwise model contains the words and trigrams which distinguish best between both
+    /// languages. It is written as `{iso1}-{iso2}.json.zip`, with both ISO 639-1 codes in the order
+    /// of the languages, and is picked up by the detector when placed in the `pairwise-models`
+    /// directory of this crate.
+    ///
+    /// `first_input_file_path`: The path to a txt file in the first language.
+    /// The assumed encoding of the txt file is UTF-8.
+    ///
+    /// `first_language`: The language of the first input file.
+    ///
+    /// `second_input_file_path`: The path to a txt file in the second language.
+    /// The assumed encoding of the txt file is UTF-8.
+    ///
+    /// `second_language`: The language of the second input file.
+    ///
+    /// `output_directory_path`: The path to an existing directory where the pairwise model file
+    /// is to be written.
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the words and trigrams are built from.
+    ///
+    /// `maximum_features`: The maximum number of words and of trigrams the pairwise model
+    /// should contain each.
+    ///
+    /// ⚠ Panics if:
+    /// - one of the input file paths is not absolute or does not point to an existing txt file
+    /// - one of the input files' encoding is not UTF-8
+    /// - the output directory path is not absolute or does not point to an existing directory
+    /// - the character class cannot be compiled to a valid regular expression
+    pub fn create_and_write_pairwise_model_file(
+        first_input_file_path: &Path,
+        first_language: &Language,
+        second_input_file_path: &Path,
+        second_language: &Language,
+        output_directory_path: &Path,
+        char_class: &str,
+        maximum_features: u32,
+    ) -> io::Result<()> {
+        check_input_file_path(first_input_file_path);
+        check_input_file_path(second_input_file_path);
+        check_output_directory_path(output_directory_path);
+
+        let first_lines = read_non_empty_lines(first_input_file_path)?;
+        let second_lines = read_non_empty_lines(second_input_file_path)?;
+        let first_lines_as_str = first_lines.iter().map(|line| line.as_str()).collect_vec();
+        let second_lines_as_str = second_lines.iter().map(|line| line.as_str()).collect_vec();
+
+        let pairwise_model = PairwiseLanguageModel::from_text(
+            &first_lines_as_str,
+            first_language,
+            &second_lines_as_str,
+            second_language,
+            char_class,
+            maximum_features as usize,
+        );
+        let (first_language, second_language) = pairwise_model.languages();
+        let file_name = format!(
+            "{}-{}.json",
+            first_language.iso_code_639_1(),
+            second_language.iso_code_639_1()
+        );
+
+        write_compressed_json(&pairwise_model.to_json(), output_directory_path, &file_name)
+    }
+
     fn create_language_model(
         input_file_path: &Path,
         language: &Language,
@@ -134,13 +248,7 @@ impl LanguageModelFilesWriter {
         char_class: &str,
         lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
     ) -> io::Result<TrainingDataLanguageModel> {
-        let file = File::open(input_file_path)?;
-        let reader = BufReader::new(file);
-        let lines = reader
-            .lines()
-            .map(|line| line.unwrap())
-            .filter(|line| !line.trim().is_empty())
-            .collect_vec();
+        let lines = read_non_empty_lines(input_file_path)?;
         let lines_as_str = lines.iter().map(|line| line.as_str()).collect_vec();
 
         Ok(TrainingDataLanguageModel::from_text(
@@ -157,15 +265,7 @@ impl LanguageModelFilesWriter {
         output_directory_path: &Path,
         file_name: &str,
     ) -> io::Result<()> {
-        let zip_file_name = format!("{}.zip", file_name);
-        let zip_file_path = output_directory_path.join(zip_file_name);
-        let zip_file = File::create(zip_file_path)?;
-        let mut zip = ZipWriter::new(zip_file);
-
-        zip.start_file(file_name, FileOptions::default())?;
-        zip.write_all(model.to_json().as_bytes())?;
-
-        Ok(())
+        write_compressed_json(&model.to_json(), output_directory_path, file_name)
     }
 }
 
@@ -279,7 +379,7 @@ impl TestDataFilesWriter {
             let removed_quotes = normalized_whitespace.replace("\"", "");
             let mut single_words = removed_quotes
                 .split(' ')
-                .map(|word| word.trim().to_lowercase())
+                .map(|word| normalize_text(word.trim()))
                 .filter(|word| word_regex.is_match(word))
                 .collect_vec();
 
@@ -334,6 +434,32 @@ impl TestDataFilesWriter {
     }
 }
 
+fn read_non_empty_lines(input_file_path: &Path) -> io::Result<Vec<String>> {
+    let file = File::open(input_file_path)?;
+    let reader = BufReader::new(file);
+    Ok(reader
+        .lines()
+        .map(|line| line.unwrap())
+        .filter(|line| !line.trim().is_empty())
+        .collect_vec())
+}
+
+fn write_compressed_json(
+    json: &str,
+    output_directory_path: &Path,
+    file_name: &str,
+) -> io::Result<()> {
+    let zip_file_name = format!("{}.zip", file_name);
+    let zip_file_path = output_directory_path.join(zip_file_name);
+    let zip_file = File::create(zip_file_path)?;
+    let mut zip = ZipWriter::new(zip_file);
+
+    zip.start_file(file_name, FileOptions::default())?;
+    zip.write_all(json.as_bytes())?;
+
+    Ok(())
+}
+
 fn check_input_file_path(input_file_path: &Path) {
     if !input_file_path.is_absolute() {
         panic!(
@@ -484,6 +610,24 @@ mod tests {
         }
         "#;
 
+        const EXPECTED_WORD_MODEL: &str = r#"
+        {
+            "language":"ENGLISH",
+            "words":{
+                "1/11":"in",
+                "1/22":"are by"
+            }
+        }
+        "#;
+
+        const EXPECTED_PAIRWISE_MODEL: &str = r#"
+        {
+            "languages":["BOSNIAN","CROATIAN"],
+            "words":{"šta":1.0986},
+            "ngrams":{"šta":1.0986}
+        }
+        "#;
+
         #[test]
         fn test_language_model_files_writer() {
             let input_file = create_temp_input_file(TEXT);
@@ -528,6 +672,57 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_word_model_file_writer() {
+            let input_file = create_temp_input_file(TEXT);
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = LanguageModelFilesWriter::create_and_write_word_model_file(
+                input_file.path(),
+                output_directory.path(),
+                &Language::English,
+                "\\p{L}",
+                3,
+            );
+
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 1);
+
+            let words_file_path = files.get(0).unwrap();
+
+            assert_file_names(words_file_path, "words.json.zip");
+            assert_file_content(words_file_path, "words.json", EXPECTED_WORD_MODEL);
+        }
+
+        #[test]
+        fn test_pairwise_model_file_writer() {
+            let first_input_file = create_temp_input_file("Što je to?\nŠto radiš?");
+            let second_input_file = create_temp_input_file("Šta je to?\nŠta radiš?");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = LanguageModelFilesWriter::create_and_write_pairwise_model_file(
+                first_input_file.path(),
+                &Language::Croatian,
+                second_input_file.path(),
+                &Language::Bosnian,
+                output_directory.path(),
+                "\\p{L}",
+                1,
+            );
+
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 1);
+
+            let pairwise_file_path = files.get(0).unwrap();
+
+            assert_file_names(pairwise_file_path, "bs-hr.json.zip");
+            assert_file_content(pairwise_file_path, "bs-hr.json", EXPECTED_PAIRWISE_MODEL);
+        }
+
         fn assert_file_names(file_path: &Path, expected_file_name: &str) {
             assert_eq!(file_path.file_name().unwrap(), expected_file_name);
         }